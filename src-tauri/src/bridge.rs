@@ -0,0 +1,169 @@
+// Frontend/backend IPC bridge for the remote-URL webview
+//
+// The main window navigates to http://localhost:{port}, so Tauri doesn't
+// expose window.__TAURI__ there and commands can't be invoked directly.
+// This module replaces the old eval-based, one-directional CustomEvent hacks
+// with a durable, bidirectional channel: Rust -> JS goes through an injected
+// client queue, JS -> Rust goes through a custom URI scheme that works
+// regardless of the page's origin.
+
+use log::{error, info, warn};
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::OnceLock;
+use tauri::{AppHandle, Emitter, Manager};
+
+/// HTTP header carrying `bridge_token()` on every inbound bridge request
+const BRIDGE_TOKEN_HEADER: &str = "x-jterm-bridge-token";
+
+/// Per-process random token embedded in the injected bridge client and
+/// required on every inbound `jterm-ipc://bridge/send` request.
+///
+/// The main window navigates to an ordinary `http://localhost` page, so
+/// Tauri's custom protocol handler has no notion of which page (or what
+/// script running on it) made a given request -- without this, anything
+/// able to reach the `jterm-ipc://` scheme could inject arbitrary
+/// `bridge://*` events into Rust-side listeners. The token is generated
+/// fresh per run and only ever handed out inside our own injected JS, so a
+/// request that doesn't carry it didn't come from our bridge client.
+fn bridge_token() -> &'static str {
+    static TOKEN: OnceLock<String> = OnceLock::new();
+    TOKEN.get_or_init(|| uuid::Uuid::new_v4().to_string())
+}
+
+/// Build the JS client injected on every page load. Buffers events emitted
+/// before a listener attaches and re-establishes itself on each full page
+/// load because `on_page_load` re-injects it every time. An in-page (SPA)
+/// route change keeps the same `window`, so the existing bridge stays in
+/// place and the re-injected script returns early.
+pub fn bridge_client_script() -> String {
+    format!(
+        r#"
+(function () {{
+    if (window.__jtermBridge) {{
+        return;
+    }}
+
+    const queue = [];
+    const listeners = {{}};
+
+    const bridge = {{
+        on(type, callback) {{
+            (listeners[type] = listeners[type] || []).push(callback);
+            queue
+                .filter((message) => message.type === type)
+                .forEach((message) => callback(message.payload));
+        }},
+        send(type, payload) {{
+            fetch('jterm-ipc://bridge/send', {{
+                method: 'POST',
+                headers: {{ '{header}': '{token}' }},
+                body: JSON.stringify({{ type, payload }}),
+            }}).catch((err) => console.error('[jterm] bridge send failed', err));
+        }},
+        __dispatch(type, payload) {{
+            const handlers = listeners[type];
+            if (handlers && handlers.length) {{
+                handlers.forEach((callback) => callback(payload));
+            }} else {{
+                queue.push({{ type, payload }});
+            }}
+        }},
+    }};
+
+    window.__jtermBridge = bridge;
+    console.log('[jterm] IPC bridge ready');
+}})();
+"#,
+        header = BRIDGE_TOKEN_HEADER,
+        token = bridge_token(),
+    )
+}
+
+/// Build the eval script that delivers one event + JSON payload into the
+/// page's bridge queue/listeners
+fn dispatch_script(event: &str, payload: &Value) -> String {
+    format!(
+        "window.__jtermBridge && window.__jtermBridge.__dispatch({}, {});",
+        serde_json::to_string(event).unwrap_or_else(|_| "\"\"".to_string()),
+        payload,
+    )
+}
+
+/// Push a structured event to the frontend's main window through the bridge
+///
+/// Any command can call this, not just menu handlers - serde handles
+/// escaping so callers never hand-format JS strings.
+pub fn emit_to_main_window<T: Serialize>(app: &AppHandle, event: &str, payload: &T) {
+    let Some(window) = app.get_webview_window("main") else {
+        warn!("No main window to emit bridge event {} to", event);
+        return;
+    };
+
+    let payload = match serde_json::to_value(payload) {
+        Ok(value) => value,
+        Err(e) => {
+            error!("Failed to serialize bridge payload for {}: {}", event, e);
+            return;
+        }
+    };
+
+    if let Err(e) = window.eval(&dispatch_script(event, &payload)) {
+        error!("Failed to dispatch bridge event {}: {}", event, e);
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct InboundMessage {
+    #[serde(rename = "type")]
+    event_type: String,
+    payload: Value,
+}
+
+/// Handle an inbound `jterm-ipc://bridge/send` request from the frontend and
+/// re-broadcast it as a normal Tauri app event, so Rust-side listeners
+/// (`app.listen(format!("bridge://{type}"), ...)`) can react the same way
+/// they would to any other Tauri event
+pub fn handle_inbound_request(
+    app: &AppHandle,
+    request: tauri::http::Request<Vec<u8>>,
+) -> tauri::http::Response<Vec<u8>> {
+    use tauri::http::{Response, StatusCode};
+
+    let token_ok = request
+        .headers()
+        .get(BRIDGE_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        == Some(bridge_token());
+
+    if !token_ok {
+        warn!("Rejected bridge inbound request with missing or invalid token");
+        return Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .body(Vec::new())
+            .unwrap();
+    }
+
+    let result: Result<InboundMessage, _> = serde_json::from_slice(request.body());
+
+    match result {
+        Ok(message) => {
+            info!("Bridge inbound event: {}", message.event_type);
+            let channel = format!("bridge://{}", message.event_type);
+            if let Err(e) = app.emit(&channel, message.payload) {
+                error!("Failed to forward bridge event to app listeners: {}", e);
+            }
+            Response::builder()
+                .status(StatusCode::NO_CONTENT)
+                .body(Vec::new())
+                .unwrap()
+        }
+        Err(e) => {
+            error!("Malformed bridge inbound request: {}", e);
+            Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Vec::new())
+                .unwrap()
+        }
+    }
+}
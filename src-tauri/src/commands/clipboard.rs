@@ -1,10 +1,17 @@
-// Clipboard commands for image copy/paste functionality
-// Uses tauri-plugin-clipboard-manager for native clipboard access
+// Clipboard commands for text/image copy/paste functionality
+// Text goes through pluggable ClipboardProvider backends so PRIMARY
+// selection (middle-click paste) works on X11/Wayland; images still go
+// through tauri-plugin-clipboard-manager since no external tool here
+// exposes an RGBA image API
 
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 use tauri::command;
 use tauri::image::Image;
+use tauri::AppHandle;
 use tauri_plugin_clipboard_manager::ClipboardExt;
-use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ImageData {
@@ -13,6 +20,242 @@ pub struct ImageData {
     pub height: u32,
 }
 
+/// Which selection a clipboard operation targets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ClipboardTarget {
+    #[default]
+    Clipboard,
+    /// X11/Wayland PRIMARY selection (middle-click paste); unsupported on macOS/Windows
+    Primary,
+}
+
+/// A backend capable of reading/writing a text clipboard target
+pub trait ClipboardProvider {
+    fn get_contents(&self, target: ClipboardTarget) -> Result<String, String>;
+    fn set_contents(&self, contents: &str, target: ClipboardTarget) -> Result<(), String>;
+}
+
+/// Falls back to the bundled Tauri plugin; only supports the regular clipboard
+struct TauriPluginProvider {
+    app: AppHandle,
+}
+
+impl ClipboardProvider for TauriPluginProvider {
+    fn get_contents(&self, target: ClipboardTarget) -> Result<String, String> {
+        if target == ClipboardTarget::Primary {
+            return Err("PRIMARY selection is not supported by this clipboard backend".to_string());
+        }
+        self.app
+            .clipboard()
+            .read_text()
+            .map_err(|e| format!("Failed to read text from clipboard: {}", e))
+    }
+
+    fn set_contents(&self, contents: &str, target: ClipboardTarget) -> Result<(), String> {
+        if target == ClipboardTarget::Primary {
+            return Err("PRIMARY selection is not supported by this clipboard backend".to_string());
+        }
+        self.app
+            .clipboard()
+            .write_text(contents.to_string())
+            .map_err(|e| format!("Failed to write text to clipboard: {}", e))
+    }
+}
+
+/// Shells out to an external clipboard tool (`pbcopy`/`pbpaste`, `wl-copy`/`wl-paste`, `xclip`, `xsel`)
+struct ExternalCommandProvider {
+    get_program: &'static str,
+    get_args: fn(ClipboardTarget) -> Vec<&'static str>,
+    set_program: &'static str,
+    set_args: fn(ClipboardTarget) -> Vec<&'static str>,
+    /// Whether the copy command forks into the background to keep owning the
+    /// selection (xclip/wl-copy); if false we wait for it to exit (pbcopy)
+    copy_detaches: bool,
+    supports_primary: bool,
+}
+
+impl ClipboardProvider for ExternalCommandProvider {
+    fn get_contents(&self, target: ClipboardTarget) -> Result<String, String> {
+        if target == ClipboardTarget::Primary && !self.supports_primary {
+            return Err("PRIMARY selection is not supported by this clipboard backend".to_string());
+        }
+
+        let output = Command::new(self.get_program)
+            .args((self.get_args)(target))
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|e| format!("Failed to run {}: {}", self.get_program, e))?;
+
+        if !output.status.success() {
+            return Err(format!(
+                "{} exited with {}: {}",
+                self.get_program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+
+    fn set_contents(&self, contents: &str, target: ClipboardTarget) -> Result<(), String> {
+        if target == ClipboardTarget::Primary && !self.supports_primary {
+            return Err("PRIMARY selection is not supported by this clipboard backend".to_string());
+        }
+
+        let mut child = Command::new(self.set_program)
+            .args((self.set_args)(target))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to run {}: {}", self.set_program, e))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(contents.as_bytes())
+                .map_err(|e| format!("Failed to write to {} stdin: {}", self.set_program, e))?;
+        }
+
+        if self.copy_detaches {
+            // xclip/wl-copy fork into the background to keep serving the
+            // selection; don't block waiting for a process that won't exit
+            Ok(())
+        } else {
+            let status = child
+                .wait()
+                .map_err(|e| format!("Failed to wait for {}: {}", self.set_program, e))?;
+            if !status.success() {
+                return Err(format!("{} exited with {}", self.set_program, status));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Check whether an external tool is on `PATH`
+fn command_exists(name: &str) -> bool {
+    Command::new("which")
+        .arg(name)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn xclip_args(target: ClipboardTarget) -> Vec<&'static str> {
+    match target {
+        ClipboardTarget::Clipboard => vec!["-selection", "clipboard"],
+        ClipboardTarget::Primary => vec!["-selection", "primary"],
+    }
+}
+
+fn xclip_out_args(target: ClipboardTarget) -> Vec<&'static str> {
+    let mut args = vec!["-o"];
+    args.extend(xclip_args(target));
+    args
+}
+
+fn wl_copy_args(target: ClipboardTarget) -> Vec<&'static str> {
+    match target {
+        ClipboardTarget::Clipboard => vec!["--type", "text/plain"],
+        ClipboardTarget::Primary => vec!["--type", "text/plain", "--primary"],
+    }
+}
+
+fn wl_paste_args(target: ClipboardTarget) -> Vec<&'static str> {
+    match target {
+        ClipboardTarget::Clipboard => vec!["--no-newline"],
+        ClipboardTarget::Primary => vec!["--no-newline", "--primary"],
+    }
+}
+
+fn no_args(_target: ClipboardTarget) -> Vec<&'static str> {
+    Vec::new()
+}
+
+/// Detect the best available clipboard backend for the current platform
+///
+/// macOS uses `pbcopy`/`pbpaste`; Wayland sessions with `wl-clipboard`
+/// installed use `wl-copy`/`wl-paste` (with `--primary` for the PRIMARY
+/// selection); X11 sessions prefer `xclip`, falling back to `xsel`. If
+/// nothing is found, falls back to the bundled Tauri plugin (clipboard
+/// target only - no PRIMARY selection support).
+fn detect_provider(app: &AppHandle) -> Box<dyn ClipboardProvider + Send + Sync> {
+    #[cfg(target_os = "macos")]
+    {
+        log::info!("[Clipboard] Using pbcopy/pbpaste backend");
+        return Box::new(ExternalCommandProvider {
+            get_program: "pbpaste",
+            get_args: no_args,
+            set_program: "pbcopy",
+            set_args: no_args,
+            copy_detaches: false,
+            supports_primary: false,
+        });
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let is_wayland = std::env::var("WAYLAND_DISPLAY").is_ok();
+
+        if is_wayland && command_exists("wl-copy") && command_exists("wl-paste") {
+            log::info!("[Clipboard] Using wl-copy/wl-paste backend");
+            return Box::new(ExternalCommandProvider {
+                get_program: "wl-paste",
+                get_args: wl_paste_args,
+                set_program: "wl-copy",
+                set_args: wl_copy_args,
+                copy_detaches: true,
+                supports_primary: true,
+            });
+        }
+
+        if command_exists("xclip") {
+            log::info!("[Clipboard] Using xclip backend");
+            return Box::new(ExternalCommandProvider {
+                get_program: "xclip",
+                get_args: xclip_out_args,
+                set_program: "xclip",
+                set_args: xclip_args,
+                copy_detaches: true,
+                supports_primary: true,
+            });
+        }
+
+        if command_exists("xsel") {
+            log::info!("[Clipboard] Using xsel backend");
+            return Box::new(ExternalCommandProvider {
+                get_program: "xsel",
+                get_args: |target| match target {
+                    ClipboardTarget::Clipboard => vec!["-b", "-o"],
+                    ClipboardTarget::Primary => vec!["-p", "-o"],
+                },
+                set_program: "xsel",
+                set_args: |target| match target {
+                    ClipboardTarget::Clipboard => vec!["-b", "-i"],
+                    ClipboardTarget::Primary => vec!["-p", "-i"],
+                },
+                copy_detaches: true,
+                supports_primary: true,
+            });
+        }
+    }
+
+    log::info!("[Clipboard] No external clipboard tool found, falling back to Tauri plugin");
+    Box::new(TauriPluginProvider { app: app.clone() })
+}
+
+static PROVIDER: OnceLock<Box<dyn ClipboardProvider + Send + Sync>> = OnceLock::new();
+
+fn provider(app: &AppHandle) -> &'static (dyn ClipboardProvider + Send + Sync) {
+    PROVIDER.get_or_init(|| detect_provider(app)).as_ref()
+}
+
 /// Read image from clipboard
 /// Returns RGBA pixel data and dimensions
 #[command]
@@ -72,36 +315,77 @@ pub async fn set_clipboard_image(
     }
 }
 
-/// Read text from clipboard
+/// Read text from clipboard, or the PRIMARY selection when `target` is `primary`
 #[command]
-pub async fn get_clipboard_text(app: tauri::AppHandle) -> Result<String, String> {
-    log::info!("[Clipboard] Reading text from clipboard");
+pub async fn get_clipboard_text(
+    app: tauri::AppHandle,
+    target: Option<ClipboardTarget>,
+) -> Result<String, String> {
+    let target = target.unwrap_or_default();
+    log::info!("[Clipboard] Reading text from {:?}", target);
 
-    match app.clipboard().read_text() {
-        Ok(text) => {
-            log::info!("[Clipboard] Successfully read {} characters", text.len());
-            Ok(text)
-        }
-        Err(e) => {
-            log::error!("[Clipboard] Failed to read text: {}", e);
-            Err(format!("Failed to read text from clipboard: {}", e))
-        }
-    }
+    provider(&app).get_contents(target).map(|text| {
+        log::info!("[Clipboard] Successfully read {} characters", text.len());
+        text
+    })
 }
 
-/// Write text to clipboard
+/// Write text to clipboard, or the PRIMARY selection when `target` is `primary`
 #[command]
-pub async fn set_clipboard_text(app: tauri::AppHandle, text: String) -> Result<(), String> {
-    log::info!("[Clipboard] Writing {} characters to clipboard", text.len());
+pub async fn set_clipboard_text(
+    app: tauri::AppHandle,
+    text: String,
+    target: Option<ClipboardTarget>,
+) -> Result<(), String> {
+    let target = target.unwrap_or_default();
+    log::info!("[Clipboard] Writing {} characters to {:?}", text.len(), target);
 
-    match app.clipboard().write_text(text) {
-        Ok(_) => {
-            log::info!("[Clipboard] Successfully wrote text to clipboard");
-            Ok(())
-        }
-        Err(e) => {
-            log::error!("[Clipboard] Failed to write text: {}", e);
-            Err(format!("Failed to write text to clipboard: {}", e))
-        }
+    provider(&app).set_contents(&text, target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xclip_args_selects_clipboard_or_primary() {
+        assert_eq!(xclip_args(ClipboardTarget::Clipboard), vec!["-selection", "clipboard"]);
+        assert_eq!(xclip_args(ClipboardTarget::Primary), vec!["-selection", "primary"]);
+    }
+
+    #[test]
+    fn test_xclip_out_args_adds_output_flag() {
+        assert_eq!(
+            xclip_out_args(ClipboardTarget::Clipboard),
+            vec!["-o", "-selection", "clipboard"]
+        );
+        assert_eq!(
+            xclip_out_args(ClipboardTarget::Primary),
+            vec!["-o", "-selection", "primary"]
+        );
+    }
+
+    #[test]
+    fn test_wl_copy_args_adds_primary_flag_only_for_primary() {
+        assert_eq!(wl_copy_args(ClipboardTarget::Clipboard), vec!["--type", "text/plain"]);
+        assert_eq!(
+            wl_copy_args(ClipboardTarget::Primary),
+            vec!["--type", "text/plain", "--primary"]
+        );
+    }
+
+    #[test]
+    fn test_wl_paste_args_adds_primary_flag_only_for_primary() {
+        assert_eq!(wl_paste_args(ClipboardTarget::Clipboard), vec!["--no-newline"]);
+        assert_eq!(
+            wl_paste_args(ClipboardTarget::Primary),
+            vec!["--no-newline", "--primary"]
+        );
+    }
+
+    #[test]
+    fn test_no_args_is_always_empty() {
+        assert!(no_args(ClipboardTarget::Clipboard).is_empty());
+        assert!(no_args(ClipboardTarget::Primary).is_empty());
     }
 }
@@ -0,0 +1,98 @@
+// Jupyter kernel lifecycle commands
+// Thin Tauri command wrappers around python::kernel::JupyterKernel
+
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+use crate::python::kernel::JupyterKernel;
+use crate::python::launcher::find_python_interpreter;
+use crate::AppState;
+
+/// Start a new Jupyter kernel and return its id
+#[tauri::command]
+pub async fn start_kernel(app: AppHandle, state: State<'_, AppState>) -> Result<String, String> {
+    let python_path = find_python_interpreter(&app)
+        .map_err(|e| format!("Failed to locate Python interpreter: {}", e))?;
+
+    let kernel = JupyterKernel::launch(&app, &python_path)
+        .await
+        .map_err(|e| format!("Failed to launch Jupyter kernel: {}", e))?;
+
+    let id = kernel.id.clone();
+    state.kernels.lock().await.insert(id.clone(), Arc::new(kernel));
+    Ok(id)
+}
+
+/// Execute code in a running kernel, streaming results via `kernel://output` events
+///
+/// Only holds the shared kernel-map lock long enough to clone out this
+/// kernel's `Arc`, then runs the (potentially long-running, blocking)
+/// execute/stream-until-idle sequence on a blocking thread. This keeps the
+/// map lock free for other kernels and leaves `interrupt_kernel` reachable
+/// for this one while a cell is still running.
+#[tauri::command]
+pub async fn execute_code(
+    app: AppHandle,
+    state: State<'_, AppState>,
+    kernel_id: String,
+    code: String,
+) -> Result<(), String> {
+    let kernel = {
+        let kernels = state.kernels.lock().await;
+        kernels
+            .get(&kernel_id)
+            .cloned()
+            .ok_or_else(|| format!("No kernel with id {}", kernel_id))?
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let msg_id = kernel
+            .execute(&code)
+            .map_err(|e| format!("Failed to submit code to kernel: {}", e))?;
+
+        kernel
+            .stream_outputs(&app, &msg_id)
+            .map_err(|e| format!("Failed to stream kernel output: {}", e))
+    })
+    .await
+    .map_err(|e| format!("Kernel execution task panicked: {}", e))?
+}
+
+/// Send SIGINT to a running kernel, interrupting any in-flight execution
+///
+/// Only clones the kernel's `Arc` out of the map, so this stays reachable
+/// even while `execute_code` has the same kernel busy on a blocking thread
+#[tauri::command]
+pub async fn interrupt_kernel(state: State<'_, AppState>, kernel_id: String) -> Result<(), String> {
+    let kernel = {
+        let kernels = state.kernels.lock().await;
+        kernels
+            .get(&kernel_id)
+            .cloned()
+            .ok_or_else(|| format!("No kernel with id {}", kernel_id))?
+    };
+
+    kernel
+        .interrupt()
+        .map_err(|e| format!("Failed to interrupt kernel: {}", e))
+}
+
+/// Shut down a kernel and remove it from the manager
+///
+/// `JupyterKernel::shutdown` polls for graceful exit via `std::thread::sleep`,
+/// so it runs on a blocking thread, same as `execute_code`
+#[tauri::command]
+pub async fn shutdown_kernel(state: State<'_, AppState>, kernel_id: String) -> Result<(), String> {
+    let kernel = {
+        let mut kernels = state.kernels.lock().await;
+        kernels.remove(&kernel_id)
+    };
+
+    if let Some(kernel) = kernel {
+        tokio::task::spawn_blocking(move || kernel.shutdown())
+            .await
+            .map_err(|e| format!("Kernel shutdown task panicked: {}", e))?
+            .map_err(|e| format!("Failed to shut down kernel: {}", e))?;
+    }
+    Ok(())
+}
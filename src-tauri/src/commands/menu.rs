@@ -1,9 +1,12 @@
 // Menu commands for native menu bar integration
 // Provides platform-specific menu operations and context menu support
 
+use log::warn;
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Manager, Runtime};
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Mutex;
+use tauri::menu::{CheckMenuItem, Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::{AppHandle, Manager, Runtime};
 
 /// Platform detection for menu customization
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -51,6 +54,16 @@ impl Platform {
             Platform::Windows | Platform::Linux => "Ctrl",
         }
     }
+
+    /// Config string (`"macos"`/`"windows"`/`"linux"`) for this platform, as used
+    /// in a `MenuNodeConfig::platform` restriction
+    fn config_name(&self) -> &'static str {
+        match self {
+            Platform::MacOS => "macos",
+            Platform::Windows => "windows",
+            Platform::Linux => "linux",
+        }
+    }
 }
 
 /// Menu item state for dynamic updates
@@ -62,15 +75,54 @@ pub struct MenuItemState {
     pub title: Option<String>,
 }
 
+/// A live, mutable native menu entry, kept around so `update_menu_item` can
+/// actually touch the menu instead of only bookkeeping state for it
+enum MenuHandle {
+    Item(MenuItem<tauri::Wry>),
+    Check(CheckMenuItem<tauri::Wry>),
+}
+
+impl MenuHandle {
+    fn set_enabled(&self, enabled: bool) -> tauri::Result<()> {
+        match self {
+            MenuHandle::Item(item) => item.set_enabled(enabled),
+            MenuHandle::Check(item) => item.set_enabled(enabled),
+        }
+    }
+
+    fn set_text(&self, text: &str) -> tauri::Result<()> {
+        match self {
+            MenuHandle::Item(item) => item.set_text(text),
+            MenuHandle::Check(item) => item.set_text(text),
+        }
+    }
+
+    fn set_checked(&self, checked: bool) -> tauri::Result<()> {
+        match self {
+            MenuHandle::Item(_) => Ok(()), // not a checkable item, silently ignored
+            MenuHandle::Check(item) => item.set_checked(checked),
+        }
+    }
+}
+
 /// Global menu state manager
+///
+/// Tracks both the last-known logical state of each item (for callers that
+/// just want to read it back) and a live handle into the native menu so
+/// updates actually take effect, plus the declarative tree the menu bar was
+/// last built from
 pub struct MenuState {
-    items: Mutex<std::collections::HashMap<String, MenuItemState>>,
+    items: Mutex<HashMap<String, MenuItemState>>,
+    handles: Mutex<HashMap<String, MenuHandle>>,
+    config: Mutex<Vec<MenuNodeConfig>>,
 }
 
 impl MenuState {
     pub fn new() -> Self {
         Self {
-            items: Mutex::new(std::collections::HashMap::new()),
+            items: Mutex::new(HashMap::new()),
+            handles: Mutex::new(HashMap::new()),
+            config: Mutex::new(Vec::new()),
         }
     }
 
@@ -83,9 +135,29 @@ impl MenuState {
         let items = self.items.lock().unwrap();
         items.get(id).cloned()
     }
+
+    /// Register the live handle for a leaf menu item built from config, so
+    /// `update_menu_item` can find it again by id
+    fn register_handle(&self, id: String, handle: MenuHandle) {
+        self.handles.lock().unwrap().insert(id, handle);
+    }
+
+    /// Replace the tree `MenuState` reflects, called once after the menu is
+    /// (re)built from config
+    fn set_config(&self, config: Vec<MenuNodeConfig>) {
+        *self.config.lock().unwrap() = config;
+    }
+
+    /// The declarative menu tree currently backing the native menu bar
+    pub fn config(&self) -> Vec<MenuNodeConfig> {
+        self.config.lock().unwrap().clone()
+    }
 }
 
 /// Update menu item state (enable/disable, check/uncheck, change title)
+///
+/// Mutates the live native menu item in addition to the bookkeeping state,
+/// so this is immediately visible in the menu bar
 #[tauri::command]
 pub async fn update_menu_item<R: Runtime>(
     app: AppHandle<R>,
@@ -94,10 +166,8 @@ pub async fn update_menu_item<R: Runtime>(
     checked: Option<bool>,
     title: Option<String>,
 ) -> Result<(), String> {
-    // Get menu state from app state
     let state = app.state::<MenuState>();
 
-    // Get current state or create new
     let mut menu_state = state.get_item(&id).unwrap_or(MenuItemState {
         id: id.clone(),
         enabled: true,
@@ -105,23 +175,32 @@ pub async fn update_menu_item<R: Runtime>(
         title: None,
     });
 
-    // Update fields
     if let Some(e) = enabled {
         menu_state.enabled = e;
     }
     if let Some(c) = checked {
         menu_state.checked = c;
     }
-    if let Some(t) = title {
-        menu_state.title = Some(t);
+    if let Some(ref t) = title {
+        menu_state.title = Some(t.clone());
     }
 
-    // Save state
     state.update_item(id.clone(), menu_state);
 
-    // Note: Actual menu update would require accessing the menu handle
-    // This is a simplified version - full implementation would need to
-    // rebuild the menu or use Tauri's menu update APIs when available
+    let handles = state.handles.lock().unwrap();
+    if let Some(handle) = handles.get(&id) {
+        if let Some(e) = enabled {
+            handle.set_enabled(e).map_err(|e| e.to_string())?;
+        }
+        if let Some(c) = checked {
+            handle.set_checked(c).map_err(|e| e.to_string())?;
+        }
+        if let Some(t) = title {
+            handle.set_text(&t).map_err(|e| e.to_string())?;
+        }
+    } else {
+        warn!("update_menu_item: no live menu handle registered for id {}", id);
+    }
 
     Ok(())
 }
@@ -135,26 +214,87 @@ pub struct ContextMenuItem {
     pub shortcut: Option<String>,
 }
 
-/// Show context menu at cursor position
+/// Show a native context menu at `(x, y)` and resolve to the selected item's
+/// id (empty string if dismissed without a selection)
 #[tauri::command]
 pub async fn show_context_menu<R: Runtime>(
-    _app: AppHandle<R>,
+    app: AppHandle<R>,
     items: Vec<ContextMenuItem>,
     x: i32,
     y: i32,
 ) -> Result<String, String> {
-    // For now, return a placeholder
-    // Full implementation would use Tauri's context menu APIs
-    // or platform-specific native menu APIs
+    let window = app
+        .get_webview_window("main")
+        .ok_or_else(|| "main window not found".to_string())?;
+
+    // Namespace each item's id uniquely to this call, so the shared
+    // `on_menu_event` dispatcher can tell a context-menu click apart from a
+    // click on the regular menu bar (which reuses plain ids like "copy")
+    let request_id = uuid::Uuid::new_v4().to_string();
+    let menu = Menu::new(&app).map_err(|e| e.to_string())?;
 
-    println!("Context menu requested at ({}, {}) with {} items", x, y, items.len());
     for item in &items {
-        println!("  - {} ({})", item.label, item.id);
+        let namespaced_id = format!("ctxmenu::{}::{}", request_id, item.id);
+        let menu_item = MenuItem::with_id(
+            &app,
+            namespaced_id,
+            &item.label,
+            item.enabled,
+            item.shortcut.as_deref(),
+        )
+        .map_err(|e| e.to_string())?;
+        menu.append(&menu_item).map_err(|e| e.to_string())?;
     }
 
-    // Return empty string to indicate no item selected
-    // In a full implementation, this would show a native menu and return the selected item ID
-    Ok(String::new())
+    let (tx, rx) = tokio::sync::oneshot::channel::<String>();
+    {
+        let mut waiters = CONTEXT_MENU_WAITERS.lock().unwrap();
+        waiters.insert(request_id.clone(), tx);
+    }
+
+    window
+        .popup_menu_at(&menu, tauri::LogicalPosition::new(x as f64, y as f64))
+        .map_err(|e| e.to_string())?;
+
+    // popup_menu_at blocks the native menu loop, so by the time it returns
+    // the click (if any) has already been dispatched to on_menu_event. But
+    // dismissing the popup without clicking anything (click-away, Escape)
+    // never fires on_menu_event at all, so this still needs a timeout --
+    // just a much shorter one, and an async await instead of a blocking recv
+    // so it doesn't stall the Tokio worker thread for everyone else.
+    let selected = tokio::time::timeout(std::time::Duration::from_millis(500), rx)
+        .await
+        .ok()
+        .and_then(|r| r.ok())
+        .unwrap_or_default();
+    CONTEXT_MENU_WAITERS.lock().unwrap().remove(&request_id);
+
+    Ok(selected)
+}
+
+/// Pending `show_context_menu` calls, keyed by their namespaced request id,
+/// waiting on the id of whichever item (if any) gets clicked
+static CONTEXT_MENU_WAITERS: Mutex<BTreeMap<String, tokio::sync::oneshot::Sender<String>>> =
+    Mutex::new(BTreeMap::new());
+
+/// Called from the app-wide `on_menu_event` handler before normal dispatch.
+/// If `event_id` belongs to an in-flight `show_context_menu` call, resolves
+/// it with the selected item's original (un-namespaced) id and returns
+/// `true` to tell the caller the event was a context-menu click, not a
+/// regular menu bar click.
+pub fn resolve_context_menu_click(event_id: &str) -> bool {
+    let Some(rest) = event_id.strip_prefix("ctxmenu::") else {
+        return false;
+    };
+    let Some((request_id, item_id)) = rest.split_once("::") else {
+        return false;
+    };
+
+    let mut waiters = CONTEXT_MENU_WAITERS.lock().unwrap();
+    if let Some(tx) = waiters.remove(request_id) {
+        let _ = tx.send(item_id.to_string());
+    }
+    true
 }
 
 /// Get platform information for menu customization
@@ -209,6 +349,252 @@ impl MenuEvent {
     }
 }
 
+/// A single node in the declarative menu tree loaded from `menu.json`
+///
+/// `node_type` is one of `"item"`, `"check"`, `"separator"`, `"predefined"`,
+/// or `"submenu"`. `accelerator` is just the key (e.g. `"N"`); the modifier
+/// is filled in from [`Platform::modifier_key`] at build time so one config
+/// produces "Cmd+N" on macOS and "Ctrl+N" on Windows/Linux.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MenuNodeConfig {
+    pub id: String,
+    #[serde(default)]
+    pub label: String,
+    #[serde(rename = "type", default = "default_node_type")]
+    pub node_type: String,
+    #[serde(default)]
+    pub accelerator: Option<String>,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    #[serde(default)]
+    pub checked: bool,
+    /// Name of the `PredefinedMenuItem` to use when `node_type == "predefined"`
+    /// (e.g. `"about"`, `"quit"`, `"hide"`, `"hide_others"`, `"show_all"`)
+    #[serde(default)]
+    pub predefined: Option<String>,
+    /// Restrict this node to a single platform (`"macos"`/`"windows"`/`"linux"`);
+    /// omit to show on all platforms
+    #[serde(default)]
+    pub platform: Option<String>,
+    #[serde(default)]
+    pub children: Vec<MenuNodeConfig>,
+}
+
+fn default_node_type() -> String {
+    "item".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Built-in menu tree, used whenever no `menu.json` override is found.
+/// Mirrors the menu that used to be hardcoded directly in `build_menu`.
+const DEFAULT_MENU_CONFIG_JSON: &str = r#"
+[
+  {
+    "id": "app_menu", "label": "jterm", "type": "submenu", "platform": "macos",
+    "children": [
+      { "id": "about", "type": "predefined", "predefined": "about" },
+      { "id": "app_sep_1", "type": "separator" },
+      { "id": "preferences", "label": "Preferences...", "type": "item", "accelerator": "," },
+      { "id": "app_sep_2", "type": "separator" },
+      { "id": "hide", "type": "predefined", "predefined": "hide" },
+      { "id": "hide_others", "type": "predefined", "predefined": "hide_others" },
+      { "id": "show_all", "type": "predefined", "predefined": "show_all" },
+      { "id": "app_sep_3", "type": "separator" },
+      { "id": "quit_app_menu", "type": "predefined", "predefined": "quit" }
+    ]
+  },
+  {
+    "id": "file_menu", "label": "File", "type": "submenu",
+    "children": [
+      { "id": "new_tab", "label": "New Tab", "type": "item", "accelerator": "N" },
+      { "id": "close_tab", "label": "Close Tab", "type": "item", "accelerator": "W" },
+      { "id": "file_sep_1", "type": "separator", "platform": "windows" },
+      { "id": "file_sep_2", "type": "separator", "platform": "linux" },
+      { "id": "quit_file_menu", "type": "predefined", "predefined": "quit", "platform": "windows" },
+      { "id": "quit_file_menu_linux", "type": "predefined", "predefined": "quit", "platform": "linux" }
+    ]
+  },
+  {
+    "id": "edit_menu", "label": "Edit", "type": "submenu",
+    "children": [
+      { "id": "copy", "label": "Copy", "type": "item", "accelerator": "C" },
+      { "id": "paste", "label": "Paste", "type": "item", "accelerator": "V" },
+      { "id": "edit_sep", "type": "separator" },
+      { "id": "clear", "label": "Clear", "type": "item" }
+    ]
+  },
+  {
+    "id": "view_menu", "label": "View", "type": "submenu",
+    "children": [
+      { "id": "show_recording_controls", "label": "Recording Controls", "type": "item" },
+      { "id": "show_performance_monitor", "label": "Performance Monitor", "type": "item" },
+      { "id": "show_ai_assistant", "label": "AI Assistant", "type": "item" }
+    ]
+  },
+  {
+    "id": "help_menu", "label": "Help", "type": "submenu", "platform": "windows",
+    "children": [ { "id": "about_help", "type": "predefined", "predefined": "about" } ]
+  },
+  {
+    "id": "help_menu_linux", "label": "Help", "type": "submenu", "platform": "linux",
+    "children": [ { "id": "about_help_linux", "type": "predefined", "predefined": "about" } ]
+  }
+]
+"#;
+
+/// Load the declarative menu tree: a `menu.json` in the current working
+/// directory takes precedence (so a project can ship its own menu), falling
+/// back to one in the app config dir, falling back to the built-in default.
+/// Mirrors the override order `commands::tasks::discover_tasks` uses for
+/// `tasks.json`.
+pub fn load_menu_config(app: &AppHandle) -> Vec<MenuNodeConfig> {
+    if let Ok(cwd_config) = std::env::current_dir().map(|d| d.join("menu.json")) {
+        if let Some(config) = read_menu_config_file(&cwd_config) {
+            return config;
+        }
+    }
+
+    if let Ok(app_config_dir) = app.path().app_config_dir() {
+        if let Some(config) = read_menu_config_file(&app_config_dir.join("menu.json")) {
+            return config;
+        }
+    }
+
+    serde_json::from_str(DEFAULT_MENU_CONFIG_JSON)
+        .expect("built-in DEFAULT_MENU_CONFIG_JSON must parse")
+}
+
+fn read_menu_config_file(path: &std::path::Path) -> Option<Vec<MenuNodeConfig>> {
+    let contents = std::fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&contents) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            warn!("Ignoring invalid menu config at {:?}: {}", path, e);
+            None
+        }
+    }
+}
+
+/// Build a real Tauri `Menu` from the declarative tree, registering a live
+/// handle for every leaf item/check box in `MenuState` so `update_menu_item`
+/// can mutate it later, and recording the tree itself on `MenuState`
+pub fn build_menu_from_config(
+    app: &AppHandle,
+    config: Vec<MenuNodeConfig>,
+) -> Result<Menu<tauri::Wry>, tauri::Error> {
+    let menu_state = app.state::<MenuState>();
+    let platform = Platform::current();
+    let menu = Menu::new(app)?;
+
+    for node in &config {
+        if let Some(entry) = build_submenu(app, &menu_state, platform, node)? {
+            menu.append(&entry)?;
+        }
+    }
+
+    menu_state.set_config(config);
+    Ok(menu)
+}
+
+fn platform_allows(platform: Platform, node: &MenuNodeConfig) -> bool {
+    match &node.platform {
+        Some(restricted) => restricted == platform.config_name(),
+        None => true,
+    }
+}
+
+fn accelerator_for(platform: Platform, node: &MenuNodeConfig) -> Option<String> {
+    node.accelerator
+        .as_ref()
+        .map(|key| format!("{}+{}", platform.modifier_key(), key))
+}
+
+fn build_submenu(
+    app: &AppHandle,
+    menu_state: &MenuState,
+    platform: Platform,
+    node: &MenuNodeConfig,
+) -> Result<Option<Submenu<tauri::Wry>>, tauri::Error> {
+    if !platform_allows(platform, node) {
+        return Ok(None);
+    }
+
+    let submenu = Submenu::new(app, &node.label, true)?;
+    for child in &node.children {
+        if let Some(item) = build_leaf(app, menu_state, platform, child)? {
+            submenu.append(&item)?;
+        }
+    }
+    Ok(Some(submenu))
+}
+
+fn build_leaf(
+    app: &AppHandle,
+    menu_state: &MenuState,
+    platform: Platform,
+    node: &MenuNodeConfig,
+) -> Result<Option<Box<dyn tauri::menu::IsMenuItem<tauri::Wry>>>, tauri::Error> {
+    if !platform_allows(platform, node) {
+        return Ok(None);
+    }
+
+    let accelerator = accelerator_for(platform, node);
+
+    let item: Box<dyn tauri::menu::IsMenuItem<tauri::Wry>> = match node.node_type.as_str() {
+        "separator" => Box::new(PredefinedMenuItem::separator(app)?),
+        "predefined" => {
+            let name = node.predefined.as_deref().unwrap_or("separator");
+            Box::new(predefined_item(app, name)?)
+        }
+        "check" => {
+            let check = CheckMenuItem::with_id(
+                app,
+                &node.id,
+                &node.label,
+                node.enabled,
+                node.checked,
+                accelerator.as_deref(),
+            )?;
+            menu_state.register_handle(node.id.clone(), MenuHandle::Check(check.clone()));
+            Box::new(check)
+        }
+        "submenu" => match build_submenu(app, menu_state, platform, node)? {
+            Some(submenu) => Box::new(submenu),
+            None => return Ok(None),
+        },
+        _ => {
+            let item = MenuItem::with_id(
+                app,
+                &node.id,
+                &node.label,
+                node.enabled,
+                accelerator.as_deref(),
+            )?;
+            menu_state.register_handle(node.id.clone(), MenuHandle::Item(item.clone()));
+            Box::new(item)
+        }
+    };
+
+    Ok(Some(item))
+}
+
+fn predefined_item(
+    app: &AppHandle,
+    name: &str,
+) -> Result<PredefinedMenuItem<tauri::Wry>, tauri::Error> {
+    match name {
+        "about" => PredefinedMenuItem::about(app, None, None),
+        "hide" => PredefinedMenuItem::hide(app, None),
+        "hide_others" => PredefinedMenuItem::hide_others(app, None),
+        "show_all" => PredefinedMenuItem::show_all(app, None),
+        "quit" => PredefinedMenuItem::quit(app, None),
+        _ => PredefinedMenuItem::separator(app),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -253,4 +639,17 @@ mod tests {
         assert!(retrieved.is_some());
         assert_eq!(retrieved.unwrap().id, "test");
     }
+
+    #[test]
+    fn test_default_menu_config_parses() {
+        let config: Vec<MenuNodeConfig> =
+            serde_json::from_str(DEFAULT_MENU_CONFIG_JSON).expect("default config must parse");
+        assert!(!config.is_empty());
+    }
+
+    #[test]
+    fn test_resolve_context_menu_click_ignores_unrelated_ids() {
+        assert!(!resolve_context_menu_click("copy"));
+        assert!(!resolve_context_menu_click("ctxmenu::missing-no-separator"));
+    }
 }
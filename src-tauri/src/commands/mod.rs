@@ -2,11 +2,17 @@
 // Exports all command handlers for the application
 
 pub mod clipboard;
+pub mod kernel;
 pub mod menu;
 pub mod system;
+pub mod tasks;
 
 // Re-export commonly used items
 #[allow(unused_imports)]
+pub use kernel::{execute_code, interrupt_kernel, shutdown_kernel, start_kernel};
+#[allow(unused_imports)]
 pub use menu::{MenuEvent, MenuState, Platform};
 #[allow(unused_imports)]
-pub use system::{app_ready, quit_app};
+pub use system::{app_ready, check_for_updates, get_auto_launch, install_update, quit_app, set_auto_launch};
+#[allow(unused_imports)]
+pub use tasks::{list_tasks, spawn_task, Task};
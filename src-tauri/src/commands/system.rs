@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
-use tauri::State;
+use tauri::{AppHandle, State};
 use log::{info, error};
 
+use crate::utils::autostart;
+use crate::utils::updater::{self, UpdateInfo};
 use crate::AppState;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -10,6 +12,7 @@ pub struct AppInfo {
     pub platform: String,
     pub backend_port: Option<u16>,
     pub database_path: Option<String>,
+    pub database_encrypted: bool,
 }
 
 /// Get application info including backend port
@@ -33,11 +36,21 @@ pub async fn app_ready(state: State<'_, AppState>) -> Result<AppInfo, String> {
         }
     };
 
+    // Check encryption status of the database on disk
+    let database_encrypted = match crate::utils::db_path::get_database_path_static() {
+        Ok(path) => crate::utils::db_crypto::is_db_encrypted(&path).unwrap_or_else(|e| {
+            error!("Failed to check database encryption status: {}", e);
+            false
+        }),
+        Err(_) => false,
+    };
+
     Ok(AppInfo {
         version: env!("CARGO_PKG_VERSION").to_string(),
         platform,
         backend_port,
         database_path,
+        database_encrypted,
     })
 }
 
@@ -70,3 +83,76 @@ pub async fn quit_app(
     app.exit(0);
     Ok(())
 }
+
+/// Check the release manifest for a newer version than the one running
+///
+/// Returns `None` when already up to date
+#[tauri::command]
+pub async fn check_for_updates(app: AppHandle) -> Result<Option<UpdateInfo>, String> {
+    updater::check_for_updates(&app)
+        .await
+        .map_err(|e| format!("Failed to check for updates: {}", e))
+}
+
+/// Download, verify, and stage the update artifact, then shut down the backend
+///
+/// Shuts down the current `PythonBackend` after the artifact is staged. The
+/// backend is not relaunched and the staged artifact is not applied here;
+/// the update takes effect only when the app is relaunched
+#[tauri::command]
+pub async fn install_update(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let backend_mutex = state.python_backend.clone();
+
+    let staged_path = updater::install_update(&app, move || async move {
+        let mut backend_guard = backend_mutex.lock().await;
+        if let Some(backend) = backend_guard.take() {
+            crate::shutdown_backend_blocking(backend).await?;
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| format!("Failed to install update: {}", e))?;
+
+    info!("Update staged at {:?}; relaunch to apply", staged_path);
+    Ok(())
+}
+
+/// Enable or disable launching jterm at system login
+#[tauri::command]
+pub async fn set_auto_launch(app: AppHandle, enabled: bool) -> Result<(), String> {
+    info!("set_auto_launch({}) command called", enabled);
+
+    let result = if enabled {
+        autostart::enable(&app)
+    } else {
+        autostart::disable(&app)
+    };
+
+    result.map_err(|e| format!("Failed to update launch-at-login setting: {}", e))
+}
+
+/// Check whether jterm is currently registered to launch at login
+#[tauri::command]
+pub async fn get_auto_launch(app: AppHandle) -> Result<bool, String> {
+    autostart::is_enabled(&app)
+        .map_err(|e| format!("Failed to read launch-at-login setting: {}", e))
+}
+
+/// Enable or disable minimizing to tray on window close
+///
+/// When disabled, closing the main window shuts down the Python backend and
+/// quits the app instead of just hiding the window
+#[tauri::command]
+pub async fn set_tray_mode_enabled(state: State<'_, AppState>, enabled: bool) -> Result<(), String> {
+    info!("set_tray_mode_enabled({}) command called", enabled);
+    state
+        .tray_mode_enabled
+        .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    Ok(())
+}
+
+/// Check whether closing the window currently minimizes to tray instead of quitting
+#[tauri::command]
+pub async fn get_tray_mode_enabled(state: State<'_, AppState>) -> Result<bool, String> {
+    Ok(state.tray_mode_enabled.load(std::sync::atomic::Ordering::Relaxed))
+}
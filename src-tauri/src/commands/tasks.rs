@@ -0,0 +1,238 @@
+// User-defined task runner
+// Discovers declarative task definitions from tasks.json and spawns them,
+// streaming their output back to the frontend as Tauri events
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use tauri::{AppHandle, Emitter, Manager};
+
+/// A single named, re-runnable command definition from `tasks.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Task {
+    pub id: String,
+    pub label: String,
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub cwd: Option<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TasksFile {
+    #[serde(default)]
+    tasks: Vec<Task>,
+}
+
+/// Which stream a line of task output came from
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum TaskStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TaskOutputEvent {
+    id: String,
+    stream: TaskStream,
+    line: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TaskExitEvent {
+    id: String,
+    code: Option<i32>,
+}
+
+fn read_tasks_file(path: &PathBuf) -> Vec<Task> {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => match serde_json::from_str::<TasksFile>(&contents) {
+            Ok(file) => file.tasks,
+            Err(e) => {
+                log::error!("[Tasks] Failed to parse {:?}: {}", path, e);
+                Vec::new()
+            }
+        },
+        Err(_) => Vec::new(),
+    }
+}
+
+/// Merge config-dir and workspace task lists, workspace entries taking
+/// precedence over config-dir entries when ids collide, sorted by label
+fn merge_tasks(config_tasks: Vec<Task>, workspace_tasks: Vec<Task>) -> Vec<Task> {
+    let mut by_id: HashMap<String, Task> = HashMap::new();
+
+    for task in config_tasks {
+        by_id.insert(task.id.clone(), task);
+    }
+    for task in workspace_tasks {
+        by_id.insert(task.id.clone(), task);
+    }
+
+    let mut tasks: Vec<Task> = by_id.into_values().collect();
+    tasks.sort_by(|a, b| a.label.cmp(&b.label));
+    tasks
+}
+
+/// Discover tasks from the workspace's `tasks.json` and the app config dir's
+/// `tasks.json`, workspace entries taking precedence when ids collide
+fn discover_tasks(app: &AppHandle) -> Vec<Task> {
+    let config_tasks = app
+        .path()
+        .app_config_dir()
+        .map(|dir| read_tasks_file(&dir.join("tasks.json")))
+        .unwrap_or_default();
+
+    let workspace_tasks = std::env::current_dir()
+        .map(|cwd| read_tasks_file(&cwd.join("tasks.json")))
+        .unwrap_or_default();
+
+    merge_tasks(config_tasks, workspace_tasks)
+}
+
+/// List all tasks discovered in the workspace and app config directories
+#[tauri::command]
+pub async fn list_tasks(app: AppHandle) -> Result<Vec<Task>, String> {
+    Ok(discover_tasks(&app))
+}
+
+/// Spawn a task by id, streaming its stdout/stderr back as Tauri events
+/// (`task://output`) and emitting `task://exit` with the final exit code
+#[tauri::command]
+pub async fn spawn_task(app: AppHandle, id: String) -> Result<(), String> {
+    let task = discover_tasks(&app)
+        .into_iter()
+        .find(|t| t.id == id)
+        .ok_or_else(|| format!("No task found with id {}", id))?;
+
+    log::info!("[Tasks] Spawning task '{}': {} {:?}", task.id, task.command, task.args);
+
+    let mut command = Command::new(&task.command);
+    command
+        .args(&task.args)
+        .envs(&task.env)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    if let Some(cwd) = &task.cwd {
+        command.current_dir(cwd);
+    }
+
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("Failed to spawn task '{}': {}", task.id, e))?;
+
+    let stdout = child.stdout.take().ok_or("Failed to capture task stdout")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture task stderr")?;
+
+    let stdout_app = app.clone();
+    let stdout_id = task.id.clone();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let _ = stdout_app.emit(
+                "task://output",
+                TaskOutputEvent {
+                    id: stdout_id.clone(),
+                    stream: TaskStream::Stdout,
+                    line,
+                },
+            );
+        }
+    });
+
+    let stderr_app = app.clone();
+    let stderr_id = task.id.clone();
+    std::thread::spawn(move || {
+        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+            let _ = stderr_app.emit(
+                "task://output",
+                TaskOutputEvent {
+                    id: stderr_id.clone(),
+                    stream: TaskStream::Stderr,
+                    line,
+                },
+            );
+        }
+    });
+
+    std::thread::spawn(move || match child.wait() {
+        Ok(status) => {
+            log::info!("[Tasks] Task '{}' exited with {}", task.id, status);
+            let _ = app.emit(
+                "task://exit",
+                TaskExitEvent {
+                    id: task.id.clone(),
+                    code: status.code(),
+                },
+            );
+        }
+        Err(e) => {
+            log::error!("[Tasks] Failed to wait for task '{}': {}", task.id, e);
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn task(id: &str, label: &str, command: &str) -> Task {
+        Task {
+            id: id.to_string(),
+            label: label.to_string(),
+            command: command.to_string(),
+            args: Vec::new(),
+            cwd: None,
+            env: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_merge_tasks_workspace_overrides_config_by_id() {
+        let config_tasks = vec![task("build", "Build (config)", "make")];
+        let workspace_tasks = vec![task("build", "Build (workspace)", "cargo build")];
+
+        let merged = merge_tasks(config_tasks, workspace_tasks);
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].command, "cargo build");
+        assert_eq!(merged[0].label, "Build (workspace)");
+    }
+
+    #[test]
+    fn test_merge_tasks_keeps_non_colliding_ids_from_both() {
+        let config_tasks = vec![task("lint", "Lint", "clippy")];
+        let workspace_tasks = vec![task("build", "Build", "cargo build")];
+
+        let merged = merge_tasks(config_tasks, workspace_tasks);
+
+        assert_eq!(merged.len(), 2);
+        assert!(merged.iter().any(|t| t.id == "lint"));
+        assert!(merged.iter().any(|t| t.id == "build"));
+    }
+
+    #[test]
+    fn test_merge_tasks_sorted_by_label() {
+        let workspace_tasks = vec![
+            task("z", "Zebra", "echo z"),
+            task("a", "Alpha", "echo a"),
+        ];
+
+        let merged = merge_tasks(Vec::new(), workspace_tasks);
+
+        assert_eq!(merged[0].label, "Alpha");
+        assert_eq!(merged[1].label, "Zebra");
+    }
+
+    #[test]
+    fn test_merge_tasks_empty_inputs() {
+        assert!(merge_tasks(Vec::new(), Vec::new()).is_empty());
+    }
+}
@@ -1,12 +1,18 @@
+mod bridge;
 mod commands;
 mod python;
 mod utils;
 
 use commands::MenuState;
-use log::{error, info};
+use log::{error, info, warn};
+use python::kernel::JupyterKernel;
 use python::launcher::PythonBackend;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
+use tauri::menu::{Menu, MenuItem};
+use tauri::tray::TrayIconBuilder;
 use tauri::Manager;
 use tokio::sync::Mutex;
 use utils::logging::DesktopLogger;
@@ -15,97 +21,318 @@ use utils::logging::DesktopLogger;
 pub struct AppState {
     python_backend: Arc<Mutex<Option<PythonBackend>>>,
     logger: Arc<DesktopLogger>,
+    /// When true, closing the main window hides it instead of quitting so the
+    /// Python backend and its port stay warm across hide/show cycles.
+    /// Defaults to enabled; toggled via `commands::system::set_tray_mode_enabled`
+    tray_mode_enabled: Arc<AtomicBool>,
+    /// Running Jupyter kernels, keyed by kernel id. Each kernel is reached
+    /// through an `Arc` so a command can clone its handle out and drop the
+    /// map lock before doing any blocking kernel I/O (see `execute_code`)
+    kernels: Arc<Mutex<HashMap<String, Arc<JupyterKernel>>>>,
 }
 
-/// Build platform-specific application menu
-fn build_menu(app: &tauri::AppHandle) -> Result<Menu<tauri::Wry>, tauri::Error> {
-    let menu = Menu::new(app)?;
-
-    // Detect platform for platform-specific menus
-    #[cfg(target_os = "macos")]
-    {
-        // macOS Application Menu (jterm)
-        let app_menu = Submenu::new(
-            app,
-            "jterm",
-            true,
-        )?;
-
-        app_menu.append(&PredefinedMenuItem::about(app, None, None)?)?;
-        app_menu.append(&PredefinedMenuItem::separator(app)?)?;
-        app_menu.append(&MenuItem::with_id(app, "preferences", "Preferences...", true, Some("Cmd+,"))?)?;
-        app_menu.append(&PredefinedMenuItem::separator(app)?)?;
-        app_menu.append(&PredefinedMenuItem::hide(app, None)?)?;
-        app_menu.append(&PredefinedMenuItem::hide_others(app, None)?)?;
-        app_menu.append(&PredefinedMenuItem::show_all(app, None)?)?;
-        app_menu.append(&PredefinedMenuItem::separator(app)?)?;
-        app_menu.append(&PredefinedMenuItem::quit(app, None)?)?;
-
-        menu.append(&app_menu)?;
+impl AppState {
+    /// Push a structured event to the frontend over the IPC bridge
+    ///
+    /// Any command can call this, not just menu handlers
+    pub fn emit_to_frontend<T: Serialize>(&self, app: &tauri::AppHandle, event: &str, payload: &T) {
+        bridge::emit_to_main_window(app, event, payload);
     }
+}
 
-    // File Menu (all platforms)
-    let file_menu = Submenu::new(app, "File", true)?;
-
-    #[cfg(target_os = "macos")]
-    let new_tab_shortcut = Some("Cmd+N");
-    #[cfg(not(target_os = "macos"))]
-    let new_tab_shortcut = Some("Ctrl+N");
+/// Show and focus the main window, e.g. in response to a tray click
+fn show_main_window(app: &tauri::AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        if let Err(e) = window.show() {
+            error!("Failed to show window from tray: {}", e);
+        }
+        if let Err(e) = window.set_focus() {
+            error!("Failed to focus window from tray: {}", e);
+        }
+    }
+}
 
-    #[cfg(target_os = "macos")]
-    let close_tab_shortcut = Some("Cmd+W");
-    #[cfg(not(target_os = "macos"))]
-    let close_tab_shortcut = Some("Ctrl+W");
+/// Build the system tray icon and its menu ("Show jterm", "New Tab", "Quit")
+fn build_tray(app: &tauri::AppHandle) -> Result<(), tauri::Error> {
+    let show_item = MenuItem::with_id(app, "tray_show", "Show jterm", true, None::<&str>)?;
+    let new_tab_item = MenuItem::with_id(app, "tray_new_tab", "New Tab", true, None::<&str>)?;
+    let quit_item = MenuItem::with_id(app, "tray_quit", "Quit", true, None::<&str>)?;
+
+    let tray_menu = Menu::with_items(app, &[&show_item, &new_tab_item, &quit_item])?;
+
+    let icon = app.default_window_icon().cloned().ok_or_else(|| {
+        tauri::Error::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "jterm requires a default window icon for the tray",
+        ))
+    })?;
+
+    TrayIconBuilder::new()
+        .icon(icon)
+        .menu(&tray_menu)
+        .show_menu_on_left_click(true)
+        .on_menu_event(|app, event| match event.id().as_ref() {
+            "tray_show" => show_main_window(app),
+            "tray_new_tab" => dispatch_menu_event(app, "new_tab"),
+            "tray_quit" => {
+                info!("Quit requested from tray");
+                tauri::async_runtime::block_on(async {
+                    let state = app.state::<AppState>();
+                    let backend_mutex = state.python_backend.clone();
+                    let mut backend_guard = backend_mutex.lock().await;
+                    if let Some(mut backend) = backend_guard.take() {
+                        if let Err(e) = backend.shutdown() {
+                            error!("Error shutting down Python backend: {}", e);
+                        }
+                    }
+                });
+                app.exit(0);
+            }
+            _ => {}
+        })
+        .on_tray_icon_event(|tray, event| {
+            if let tauri::tray::TrayIconEvent::Click {
+                button: tauri::tray::MouseButton::Left,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        })
+        .build(app)?;
 
-    file_menu.append(&MenuItem::with_id(app, "new_tab", "New Tab", true, new_tab_shortcut)?)?;
-    file_menu.append(&MenuItem::with_id(app, "close_tab", "Close Tab", true, close_tab_shortcut)?)?;
+    Ok(())
+}
 
-    #[cfg(not(target_os = "macos"))]
-    {
-        file_menu.append(&PredefinedMenuItem::separator(app)?)?;
-        file_menu.append(&PredefinedMenuItem::quit(app, None)?)?;
+/// Dispatch a menu/tray event id to the frontend over the IPC bridge, so
+/// tray items reuse the same route as native menu clicks
+///
+/// A click on a `show_context_menu`-built popup is routed back to the
+/// waiting command instead of the frontend, since it's answering a
+/// request/response call rather than firing a fire-and-forget menu event
+fn dispatch_menu_event(app: &tauri::AppHandle, event_id: &str) {
+    if commands::menu::resolve_context_menu_click(event_id) {
+        return;
     }
 
-    menu.append(&file_menu)?;
+    info!("Menu event: {}", event_id);
+    bridge::emit_to_main_window(app, "menu", &serde_json::json!({ "id": event_id }));
+}
 
-    // Edit Menu (all platforms)
-    let edit_menu = Submenu::new(app, "Edit", true)?;
+/// Shut down a dead/unhealthy backend on a blocking thread
+///
+/// `PythonBackend::shutdown` waits on `std::thread::sleep` in a loop for up
+/// to a few seconds of graceful-exit polling; running it directly inside a
+/// `tauri::async_runtime::spawn`-ed task (as the watchdog and crash
+/// supervisor do) would park a Tokio worker thread for that long
+pub(crate) async fn shutdown_backend_blocking(mut backend: PythonBackend) -> Result<(), anyhow::Error> {
+    tokio::task::spawn_blocking(move || backend.shutdown())
+        .await
+        .map_err(|e| anyhow::anyhow!("Backend shutdown task panicked: {}", e))?
+}
 
-    #[cfg(target_os = "macos")]
-    let copy_shortcut = Some("Cmd+C");
-    #[cfg(not(target_os = "macos"))]
-    let copy_shortcut = Some("Ctrl+C");
+/// Interval between watchdog health checks once the backend is ready
+const WATCHDOG_INTERVAL_SECS: u64 = 10;
+/// Consecutive failed checks before the watchdog tears down and relaunches the backend
+const WATCHDOG_FAILURE_THRESHOLD: u32 = 3;
 
-    #[cfg(target_os = "macos")]
-    let paste_shortcut = Some("Cmd+V");
-    #[cfg(not(target_os = "macos"))]
-    let paste_shortcut = Some("Ctrl+V");
+/// Long-running task that periodically checks backend health after it's
+/// ready, and relaunches it (re-navigating the main window) if it dies
+fn spawn_backend_watchdog(
+    app_handle: tauri::AppHandle,
+    backend_mutex: Arc<Mutex<Option<PythonBackend>>>,
+    logger: Arc<DesktopLogger>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut consecutive_failures = 0u32;
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(WATCHDOG_INTERVAL_SECS)).await;
+
+            let port = {
+                let backend_guard = backend_mutex.lock().await;
+                match backend_guard.as_ref() {
+                    Some(backend) => backend.port(),
+                    None => continue, // backend already torn down elsewhere (e.g. quitting)
+                }
+            };
 
-    edit_menu.append(&MenuItem::with_id(app, "copy", "Copy", true, copy_shortcut)?)?;
-    edit_menu.append(&MenuItem::with_id(app, "paste", "Paste", true, paste_shortcut)?)?;
-    edit_menu.append(&PredefinedMenuItem::separator(app)?)?;
-    edit_menu.append(&MenuItem::with_id(app, "clear", "Clear", true, None::<&str>)?)?;
+            match python::health::check_backend_health(port).await {
+                Ok(()) => {
+                    consecutive_failures = 0;
+                }
+                Err(e) => {
+                    consecutive_failures += 1;
+                    warn!(
+                        "Backend watchdog check failed ({}/{}): {}",
+                        consecutive_failures, WATCHDOG_FAILURE_THRESHOLD, e
+                    );
+
+                    if consecutive_failures < WATCHDOG_FAILURE_THRESHOLD {
+                        continue;
+                    }
 
-    menu.append(&edit_menu)?;
+                    error!("Backend exceeded failure threshold, relaunching...");
+                    logger.log_error("Python backend failed health checks, relaunching");
 
-    // View Menu (all platforms)
-    let view_menu = Submenu::new(app, "View", true)?;
+                    let mut backend_guard = backend_mutex.lock().await;
+                    if let Some(dead_backend) = backend_guard.take() {
+                        if let Err(e) = shutdown_backend_blocking(dead_backend).await {
+                            warn!("Error shutting down unhealthy backend: {}", e);
+                        }
+                    }
+                    // Drop the guard before the (up to ~30s) relaunch so other
+                    // callers waiting on `state.python_backend` -- app_ready,
+                    // quit_app, install_update, the tray quit handler, the
+                    // crash supervisor -- aren't blocked for the relaunch
+                    drop(backend_guard);
+
+                    match PythonBackend::launch(&app_handle).await {
+                        Ok(new_backend) => {
+                            let new_port = new_backend.port();
+                            let base_url = new_backend.base_url().to_string();
+                            *backend_mutex.lock().await = Some(new_backend);
+
+                            logger.log_python_backend_ready(new_port);
+                            info!("Backend relaunched by watchdog on port {}", new_port);
+
+                            if let Some(window) = app_handle.get_webview_window("main") {
+                                if let Err(e) = window.navigate(tauri::Url::parse(&base_url).unwrap()) {
+                                    error!("Failed to re-navigate after watchdog relaunch: {}", e);
+                                }
+                            }
 
-    view_menu.append(&MenuItem::with_id(app, "show_recording_controls", "Recording Controls", true, None::<&str>)?)?;
-    view_menu.append(&MenuItem::with_id(app, "show_performance_monitor", "Performance Monitor", true, None::<&str>)?)?;
-    view_menu.append(&MenuItem::with_id(app, "show_ai_assistant", "AI Assistant", true, None::<&str>)?)?;
+                            bridge::emit_to_main_window(
+                                &app_handle,
+                                "backend-relaunched",
+                                &serde_json::json!({ "port": new_port }),
+                            );
 
-    menu.append(&view_menu)?;
+                            consecutive_failures = 0;
+                        }
+                        Err(e) => {
+                            error!("Watchdog failed to relaunch backend: {}", e);
+                            logger.log_error(&format!("Watchdog failed to relaunch backend: {}", e));
+                        }
+                    }
+                }
+            }
+        }
+    });
+}
 
-    // Help Menu (Windows/Linux only - macOS uses app menu)
-    #[cfg(not(target_os = "macos"))]
-    {
-        let help_menu = Submenu::new(app, "Help", true)?;
-        help_menu.append(&PredefinedMenuItem::about(app, None, None)?)?;
-        menu.append(&help_menu)?;
-    }
+/// Interval between crash-supervisor liveness polls
+const SUPERVISOR_POLL_INTERVAL_SECS: u64 = 2;
+/// Give up auto-relaunching after this many consecutive crash-relaunch attempts
+const SUPERVISOR_MAX_RETRIES: u32 = 5;
+/// Backoff base/cap for spacing out repeated relaunch attempts after a crash
+const SUPERVISOR_BACKOFF_BASE_MS: u64 = 500;
+const SUPERVISOR_BACKOFF_CAP_MS: u64 = 30_000;
+
+/// Long-running task that polls `PythonBackend::is_running()` and relaunches
+/// the backend if it exits unexpectedly (distinct from `spawn_backend_watchdog`,
+/// which relaunches on repeated HTTP health-check failures even if the
+/// process itself is still alive, e.g. hung/unresponsive)
+fn spawn_backend_crash_supervisor(
+    app_handle: tauri::AppHandle,
+    backend_mutex: Arc<Mutex<Option<PythonBackend>>>,
+    logger: Arc<DesktopLogger>,
+) {
+    tauri::async_runtime::spawn(async move {
+        let mut retries = 0u32;
+        // Set when this supervisor's own relaunch failed, leaving no backend in state
+        let mut relaunch_failed = false;
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(SUPERVISOR_POLL_INTERVAL_SECS)).await;
+
+            let crashed = {
+                let backend_guard = backend_mutex.lock().await;
+                match backend_guard.as_ref() {
+                    Some(backend) => !backend.is_running(),
+                    // Torn down elsewhere (e.g. quitting), unless our own relaunch failed
+                    None => relaunch_failed,
+                }
+            };
 
-    Ok(menu)
+            if !crashed {
+                retries = 0;
+                relaunch_failed = false;
+                continue;
+            }
+
+            if retries >= SUPERVISOR_MAX_RETRIES {
+                error!(
+                    "Backend crash supervisor: exceeded max retries ({}), giving up",
+                    SUPERVISOR_MAX_RETRIES
+                );
+                logger.log_error("Python backend crashed repeatedly; crash supervisor giving up");
+                continue;
+            }
+
+            let delay_ms = ((SUPERVISOR_BACKOFF_BASE_MS as f64) * 2f64.powi(retries as i32))
+                .min(SUPERVISOR_BACKOFF_CAP_MS as f64) as u64;
+            warn!(
+                "Backend crash supervisor: backend exited unexpectedly, retry {}/{} in {}ms",
+                retries + 1,
+                SUPERVISOR_MAX_RETRIES,
+                delay_ms
+            );
+            tokio::time::sleep(std::time::Duration::from_millis(delay_ms)).await;
+
+            let mut backend_guard = backend_mutex.lock().await;
+            // Drop the dead handle; shutdown() is a no-op if the process already exited
+            if let Some(dead_backend) = backend_guard.take() {
+                if let Err(e) = shutdown_backend_blocking(dead_backend).await {
+                    warn!("Error cleaning up crashed backend: {}", e);
+                }
+            }
+            // Drop the guard before the (up to ~30s) relaunch so other
+            // callers waiting on `state.python_backend` aren't blocked
+            drop(backend_guard);
+
+            match PythonBackend::launch(&app_handle).await {
+                Ok(new_backend) => {
+                    let new_port = new_backend.port();
+                    let base_url = new_backend.base_url().to_string();
+                    *backend_mutex.lock().await = Some(new_backend);
+
+                    retries += 1;
+                    relaunch_failed = false;
+                    logger.log_error(&format!(
+                        "Python backend crashed, relaunched on port {} (retry {}/{})",
+                        new_port, retries, SUPERVISOR_MAX_RETRIES
+                    ));
+                    info!("Backend relaunched by crash supervisor on port {}", new_port);
+
+                    if let Some(window) = app_handle.get_webview_window("main") {
+                        if let Err(e) = window.navigate(tauri::Url::parse(&base_url).unwrap()) {
+                            error!("Failed to re-navigate after crash relaunch: {}", e);
+                        }
+                    }
+
+                    bridge::emit_to_main_window(
+                        &app_handle,
+                        "backend-crash-relaunched",
+                        &serde_json::json!({ "port": new_port, "retry": retries }),
+                    );
+                }
+                Err(e) => {
+                    retries += 1;
+                    relaunch_failed = true;
+                    error!("Crash supervisor failed to relaunch backend: {}", e);
+                    logger.log_error(&format!("Crash supervisor failed to relaunch backend: {}", e));
+                }
+            }
+        }
+    });
+}
+
+/// Build the application menu from the declarative tree in `menu.json` (or
+/// the built-in default if none is found), rather than a hardcoded layout
+fn build_menu(app: &tauri::AppHandle) -> Result<Menu<tauri::Wry>, tauri::Error> {
+    let config = commands::menu::load_menu_config(app);
+    commands::menu::build_menu_from_config(app, config)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -117,9 +344,18 @@ pub fn run() {
                 .build(),
         )
         .plugin(tauri_plugin_clipboard_manager::init())
+        .register_uri_scheme_protocol("jterm-ipc", |ctx, request| {
+            bridge::handle_inbound_request(ctx.app_handle(), request)
+        })
         .invoke_handler(tauri::generate_handler![
             commands::system::app_ready,
             commands::system::quit_app,
+            commands::system::check_for_updates,
+            commands::system::install_update,
+            commands::system::set_auto_launch,
+            commands::system::get_auto_launch,
+            commands::system::set_tray_mode_enabled,
+            commands::system::get_tray_mode_enabled,
             commands::menu::update_menu_item,
             commands::menu::show_context_menu,
             commands::menu::get_platform_info,
@@ -127,6 +363,12 @@ pub fn run() {
             commands::clipboard::set_clipboard_image,
             commands::clipboard::get_clipboard_text,
             commands::clipboard::set_clipboard_text,
+            commands::tasks::list_tasks,
+            commands::tasks::spawn_task,
+            commands::kernel::start_kernel,
+            commands::kernel::execute_code,
+            commands::kernel::interrupt_kernel,
+            commands::kernel::shutdown_kernel,
         ])
         .setup(|app| {
             info!("jterm desktop application initializing...");
@@ -146,6 +388,8 @@ pub fn run() {
             let app_state = AppState {
                 python_backend: Arc::new(Mutex::new(None)),
                 logger: logger.clone(),
+                tray_mode_enabled: Arc::new(AtomicBool::new(true)),
+                kernels: Arc::new(Mutex::new(HashMap::new())),
             };
 
             app.manage(app_state);
@@ -165,6 +409,11 @@ pub fn run() {
                 }
             }
 
+            // Build the system tray so closing the window can minimize instead of quitting
+            if let Err(e) = build_tray(&app.handle()) {
+                error!("Failed to build system tray: {}", e);
+            }
+
             // Launch Python backend asynchronously
             let app_handle = app.handle().clone();
             let backend_mutex = app.state::<AppState>().python_backend.clone();
@@ -221,6 +470,29 @@ pub fn run() {
                                 }
                             }
                         }
+
+                        // Watch the backend and transparently relaunch it if it crashes
+                        spawn_backend_watchdog(app_handle.clone(), backend_mutex.clone(), logger_clone.clone());
+
+                        // Separately, watch for the process itself disappearing
+                        // (as opposed to just failing health checks) and relaunch it
+                        spawn_backend_crash_supervisor(app_handle.clone(), backend_mutex.clone(), logger_clone.clone());
+
+                        // In development, restart the backend when its source changes
+                        #[cfg(debug_assertions)]
+                        {
+                            let backend_guard = backend_mutex.lock().await;
+                            if let Some(backend) = backend_guard.as_ref() {
+                                let app_root = backend.app_root().clone();
+                                drop(backend_guard);
+                                python::watcher::spawn_dev_watcher(
+                                    app_handle.clone(),
+                                    backend_mutex.clone(),
+                                    logger_clone.clone(),
+                                    app_root,
+                                );
+                            }
+                        }
                     }
                     Err(e) => {
                         error!("Failed to launch Python backend: {}", e);
@@ -244,38 +516,25 @@ pub fn run() {
             Ok(())
         })
         .on_menu_event(|app, event| {
-            let event_id = event.id().as_ref();
-            info!("Menu event: {}", event_id);
-
-            // Get the main window to send events to frontend
-            if let Some(window) = app.get_webview_window("main") {
-                // Use eval to dispatch a custom DOM event instead of Tauri event
-                // This works with remote URLs like http://localhost:8000
-                let script = format!(
-                    r#"
-                    (function() {{
-                        const event = new CustomEvent('tauri-menu-event', {{
-                            detail: {{ id: '{}' }}
-                        }});
-                        window.dispatchEvent(event);
-                        console.log('[Tauri] Dispatched menu event:', '{}');
-                    }})();
-                    "#,
-                    event_id, event_id
-                );
-
-                if let Err(e) = window.eval(&script) {
-                    error!("Failed to dispatch menu event: {}", e);
-                }
-            }
+            dispatch_menu_event(app, event.id().as_ref());
         })
         .on_window_event(|window, event| {
             match event {
-                tauri::WindowEvent::CloseRequested { .. } => {
+                tauri::WindowEvent::CloseRequested { api, .. } => {
+                    let app_state = window.state::<AppState>();
+
+                    if app_state.tray_mode_enabled.load(Ordering::Relaxed) {
+                        info!("Window close requested, minimizing to tray...");
+                        api.prevent_close();
+                        if let Err(e) = window.hide() {
+                            error!("Failed to hide window for tray mode: {}", e);
+                        }
+                        return;
+                    }
+
                     info!("Window close requested, shutting down...");
 
                     // Shutdown Python backend
-                    let app_state = window.state::<AppState>();
                     let backend_mutex = app_state.python_backend.clone();
                     let logger = app_state.logger.clone();
 
@@ -294,6 +553,12 @@ pub fn run() {
             }
         })
         .on_page_load(|webview, _payload| {
+            // Inject the durable IPC bridge client first so menu/tray events
+            // dispatched right after navigation don't get dropped
+            if let Err(e) = webview.eval(&bridge::bridge_client_script()) {
+                error!("Failed to inject IPC bridge client: {}", e);
+            }
+
             // Inject debug script to verify Tauri API availability
             let script = r#"
                 console.log('[Tauri Page Load] Checking API availability...');
@@ -1,5 +1,6 @@
 use anyhow::Result;
 use log::{debug, info, warn};
+use rand::Rng;
 use std::time::Duration;
 use tokio::time::sleep;
 
@@ -7,19 +8,53 @@ use tokio::time::sleep;
 pub struct HealthCheckConfig {
     pub max_attempts: u32,
     pub timeout_secs: u64,
-    pub retry_delay_ms: u64,
+    /// Base delay in milliseconds for attempt 1 of the backoff schedule
+    pub base_delay_ms: u64,
+    /// Upper bound on the computed delay before jitter is applied
+    pub max_delay_ms: u64,
+    /// Backoff growth factor; `1.0` keeps the original fixed-delay behavior
+    pub multiplier: f64,
 }
 
 impl Default for HealthCheckConfig {
     fn default() -> Self {
         Self {
-            max_attempts: 30,      // 30 attempts
-            timeout_secs: 30,      // 30 second total timeout
-            retry_delay_ms: 1000,  // 1 second between attempts
+            max_attempts: 30,     // 30 attempts
+            timeout_secs: 30,     // 30 second total timeout
+            base_delay_ms: 1000,  // 1 second base delay
+            max_delay_ms: 10_000, // cap backoff at 10 seconds
+            multiplier: 1.0,      // fixed delay by default
         }
     }
 }
 
+impl HealthCheckConfig {
+    /// Exponential backoff with full jitter: the delay for attempt `n` is
+    /// `min(cap, base * multiplier^(n-1))`, and the actual sleep is a
+    /// uniform random value in `[0, that_delay]` to avoid thundering-herd
+    /// reconnects when `multiplier > 1.0`. When `multiplier <= 1.0` the
+    /// delay never grows and jitter is skipped; at exactly `1.0` this keeps
+    /// the original fixed-delay behavior (a steady `base_delay_ms` between
+    /// attempts), while below `1.0` the delay shrinks with each attempt.
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponent = (attempt - 1) as i32;
+        let raw_delay = self.base_delay_ms as f64 * self.multiplier.powi(exponent);
+        let capped_delay = raw_delay.min(self.max_delay_ms as f64).max(0.0) as u64;
+
+        if self.multiplier <= 1.0 {
+            return Duration::from_millis(capped_delay);
+        }
+
+        let jittered = if capped_delay == 0 {
+            0
+        } else {
+            rand::thread_rng().gen_range(0..=capped_delay)
+        };
+
+        Duration::from_millis(jittered)
+    }
+}
+
 /// Check if the Python backend is ready by polling the health endpoint
 ///
 /// Polls http://localhost:{port}/health until it returns 200 OK or timeout is reached
@@ -61,7 +96,7 @@ pub async fn wait_for_backend_ready(port: u16, config: Option<HealthCheckConfig>
         }
 
         if attempt < config.max_attempts {
-            sleep(Duration::from_millis(config.retry_delay_ms)).await;
+            sleep(config.backoff_delay(attempt)).await;
         }
     }
 
@@ -138,4 +173,54 @@ mod tests {
         let available = is_port_available(65535).await;
         assert!(available || !available); // Always passes, just testing the function runs
     }
+
+    #[test]
+    fn test_backoff_delay_is_fixed_when_multiplier_is_one() {
+        let config = HealthCheckConfig {
+            max_attempts: 30,
+            timeout_secs: 30,
+            base_delay_ms: 1000,
+            max_delay_ms: 10_000,
+            multiplier: 1.0,
+        };
+
+        for attempt in 1..=5 {
+            assert_eq!(
+                config.backoff_delay(attempt),
+                Duration::from_millis(1000),
+                "attempt {} should sleep the fixed base delay with no jitter",
+                attempt
+            );
+        }
+    }
+
+    #[test]
+    fn test_backoff_delay_grows_and_caps_with_multiplier() {
+        let config = HealthCheckConfig {
+            max_attempts: 30,
+            timeout_secs: 30,
+            base_delay_ms: 100,
+            max_delay_ms: 1000,
+            multiplier: 2.0,
+        };
+
+        // Attempt 1: raw delay 100ms, jittered into [0, 100]
+        assert!(config.backoff_delay(1) <= Duration::from_millis(100));
+
+        // Attempt 10 would be way past the cap before jitter is applied
+        assert!(config.backoff_delay(10) <= Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_backoff_delay_zero_base_is_zero() {
+        let config = HealthCheckConfig {
+            max_attempts: 30,
+            timeout_secs: 30,
+            base_delay_ms: 0,
+            max_delay_ms: 10_000,
+            multiplier: 2.0,
+        };
+
+        assert_eq!(config.backoff_delay(1), Duration::from_millis(0));
+    }
 }
@@ -0,0 +1,511 @@
+// Jupyter kernel subsystem
+// Launches ipykernel processes and speaks the Jupyter wire protocol over
+// ZeroMQ so terminal/editor code can be executed against a live kernel
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use log::{debug, error, info, warn};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use shared_child::SharedChild;
+use std::process::{Command, Stdio};
+use std::sync::Mutex as StdMutex;
+use tauri::{AppHandle, Emitter};
+use uuid::Uuid;
+
+use crate::commands::clipboard::ImageData;
+use crate::python::health::find_available_port;
+use crate::utils::db_path::get_temp_directory;
+
+const PROTOCOL_VERSION: &str = "5.3";
+const DELIMITER: &[u8] = b"<IDS|MSG>";
+
+/// Connection file written for `ipykernel_launcher`, mirroring the five
+/// ZeroMQ channels a Jupyter kernel exposes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ConnectionInfo {
+    shell_port: u16,
+    iopub_port: u16,
+    stdin_port: u16,
+    control_port: u16,
+    hb_port: u16,
+    ip: String,
+    key: String,
+    transport: String,
+    signature_scheme: String,
+    kernel_name: String,
+}
+
+/// Rich output emitted while a kernel is running
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum KernelOutput {
+    Stream { name: String, text: String },
+    Result { mime_bundle: serde_json::Value },
+    Image { image: ImageData },
+    Error { ename: String, evalue: String, traceback: Vec<String> },
+    Idle,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct KernelOutputEvent {
+    kernel_id: String,
+    output: KernelOutput,
+}
+
+/// A running Jupyter kernel process plus its ZeroMQ channels
+///
+/// Wrapped in `Arc` by callers (see `commands::kernel`) so a long-running
+/// `execute`/`stream_outputs` call doesn't have to hold the shared kernel
+/// map lock, and `interrupt`/`shutdown` stay reachable while it's in flight.
+/// The sockets are behind a plain `Mutex` (not `tokio::sync::Mutex`) purely
+/// so `JupyterKernel` is `Sync` and therefore `Arc`-shareable across
+/// threads/tasks — `zmq::Socket` itself is `Send` but not `Sync`. Socket
+/// access from `execute`/`stream_outputs` happens on a blocking thread (see
+/// `commands::kernel::execute_code`), so a std mutex is appropriate.
+pub struct JupyterKernel {
+    pub id: String,
+    process: SharedChild,
+    connection: ConnectionInfo,
+    context: zmq::Context,
+    shell_socket: StdMutex<zmq::Socket>,
+    iopub_socket: StdMutex<zmq::Socket>,
+    session_id: String,
+}
+
+fn hmac_sign(key: &str, parts: &[&[u8]]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key.as_bytes())
+        .expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+    }
+    hex::encode(mac.finalize().into_bytes())
+}
+
+impl JupyterKernel {
+    /// Launch `python -m ipykernel_launcher -f <connection.json>`, give it a
+    /// fixed 500ms to bind its ZeroMQ channels (readiness is not checked),
+    /// connect to them, and return a handle to it
+    pub async fn launch(app_handle: &AppHandle, python_path: &std::path::Path) -> Result<Self> {
+        let shell_port = find_available_port(49_152, 65_000)
+            .await
+            .context("No available port for kernel shell channel")?;
+        let iopub_port = find_available_port(shell_port + 1, 65_000)
+            .await
+            .context("No available port for kernel iopub channel")?;
+        let stdin_port = find_available_port(iopub_port + 1, 65_000)
+            .await
+            .context("No available port for kernel stdin channel")?;
+        let control_port = find_available_port(stdin_port + 1, 65_000)
+            .await
+            .context("No available port for kernel control channel")?;
+        let hb_port = find_available_port(control_port + 1, 65_000)
+            .await
+            .context("No available port for kernel heartbeat channel")?;
+
+        let connection = ConnectionInfo {
+            shell_port,
+            iopub_port,
+            stdin_port,
+            control_port,
+            hb_port,
+            ip: "127.0.0.1".to_string(),
+            key: Uuid::new_v4().to_string(),
+            transport: "tcp".to_string(),
+            signature_scheme: "hmac-sha256".to_string(),
+            kernel_name: "python3".to_string(),
+        };
+
+        let temp_dir = get_temp_directory(app_handle)?;
+        let kernel_id = Uuid::new_v4().to_string();
+        let connection_path = temp_dir.join(format!("kernel-{}.json", kernel_id));
+        std::fs::write(
+            &connection_path,
+            serde_json::to_string_pretty(&connection)?,
+        )
+        .with_context(|| format!("Failed to write connection file at {:?}", connection_path))?;
+
+        info!("Launching Jupyter kernel {} via {:?}", kernel_id, python_path);
+
+        let mut command = Command::new(python_path);
+        command
+            .arg("-m")
+            .arg("ipykernel_launcher")
+            .arg("-f")
+            .arg(&connection_path)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let process = SharedChild::spawn(&mut command).context("Failed to spawn ipykernel_launcher")?;
+
+        // Give the kernel a moment to bind its ZeroMQ sockets before we connect
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+
+        let context = zmq::Context::new();
+        let shell_socket = context
+            .socket(zmq::DEALER)
+            .context("Failed to create shell socket")?;
+        shell_socket
+            .connect(&format!("tcp://{}:{}", connection.ip, connection.shell_port))
+            .context("Failed to connect to kernel shell channel")?;
+
+        let iopub_socket = context
+            .socket(zmq::SUB)
+            .context("Failed to create iopub socket")?;
+        iopub_socket
+            .connect(&format!("tcp://{}:{}", connection.ip, connection.iopub_port))
+            .context("Failed to connect to kernel iopub channel")?;
+        iopub_socket
+            .set_subscribe(b"")
+            .context("Failed to subscribe to kernel iopub channel")?;
+
+        Ok(Self {
+            id: kernel_id,
+            process,
+            connection,
+            context,
+            shell_socket: StdMutex::new(shell_socket),
+            iopub_socket: StdMutex::new(iopub_socket),
+            session_id: Uuid::new_v4().to_string(),
+        })
+    }
+
+    /// Build and sign a Jupyter wire-protocol message envelope
+    fn build_message(&self, msg_type: &str, content: serde_json::Value) -> (String, Vec<Vec<u8>>) {
+        let msg_id = Uuid::new_v4().to_string();
+
+        let header = serde_json::json!({
+            "msg_id": msg_id,
+            "username": "jterm",
+            "session": self.session_id,
+            "date": "",
+            "msg_type": msg_type,
+            "version": PROTOCOL_VERSION,
+        });
+        let parent_header = serde_json::json!({});
+        let metadata = serde_json::json!({});
+
+        let header_bytes = header.to_string().into_bytes();
+        let parent_bytes = parent_header.to_string().into_bytes();
+        let metadata_bytes = metadata.to_string().into_bytes();
+        let content_bytes = content.to_string().into_bytes();
+
+        let signature = hmac_sign(
+            &self.connection.key,
+            &[&header_bytes, &parent_bytes, &metadata_bytes, &content_bytes],
+        );
+
+        let frames = vec![
+            DELIMITER.to_vec(),
+            signature.into_bytes(),
+            header_bytes,
+            parent_bytes,
+            metadata_bytes,
+            content_bytes,
+        ];
+
+        (msg_id, frames)
+    }
+
+    /// Send `execute_request` on the shell channel and return its `msg_id`
+    pub fn execute(&self, code: &str) -> Result<String> {
+        let content = serde_json::json!({
+            "code": code,
+            "silent": false,
+            "store_history": true,
+            "user_expressions": {},
+            "allow_stdin": false,
+        });
+
+        let (msg_id, frames) = self.build_message("execute_request", content);
+        self.shell_socket
+            .lock()
+            .unwrap()
+            .send_multipart(frames, 0)
+            .context("Failed to send execute_request")?;
+
+        debug!("Sent execute_request {} to kernel {}", msg_id, self.id);
+        Ok(msg_id)
+    }
+
+    /// Consume `iopub` messages for the given `msg_id` until an idle status
+    /// is observed, emitting decoded output as Tauri events
+    ///
+    /// This blocks (`recv_multipart` has no timeout) until the kernel goes
+    /// idle, so callers must run it via `spawn_blocking` rather than on a
+    /// tokio worker thread — see `commands::kernel::execute_code`
+    pub fn stream_outputs(&self, app: &AppHandle, msg_id: &str) -> Result<()> {
+        loop {
+            let frames = self
+                .iopub_socket
+                .lock()
+                .unwrap()
+                .recv_multipart(0)
+                .context("Failed to receive iopub message")?;
+
+            let Some(output) = decode_iopub_message(&frames, msg_id) else {
+                continue;
+            };
+
+            let is_idle = matches!(output, KernelOutput::Idle);
+
+            let _ = app.emit(
+                "kernel://output",
+                KernelOutputEvent {
+                    kernel_id: self.id.clone(),
+                    output,
+                },
+            );
+
+            if is_idle {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Send SIGINT to the kernel process, interrupting any running execution
+    pub fn interrupt(&self) -> Result<()> {
+        #[cfg(unix)]
+        {
+            unsafe {
+                libc::kill(self.process.id() as i32, libc::SIGINT);
+            }
+            Ok(())
+        }
+
+        #[cfg(not(unix))]
+        {
+            warn!("Kernel interrupt is not implemented on this platform");
+            Ok(())
+        }
+    }
+
+    /// Gracefully shut down the kernel process
+    ///
+    /// Takes `&self` (not `&mut self`) since the kernel is reached through an
+    /// `Arc` shared with the kernel map, so callers can't get exclusive
+    /// access; `SharedChild` lets us signal/wait without one
+    pub fn shutdown(&self) -> Result<()> {
+        info!("Shutting down Jupyter kernel {}", self.id);
+
+        #[cfg(unix)]
+        {
+            unsafe {
+                libc::kill(self.process.id() as i32, libc::SIGTERM);
+            }
+            for _ in 0..50 {
+                match self.process.try_wait() {
+                    Ok(Some(_)) => return Ok(()),
+                    Ok(None) => std::thread::sleep(std::time::Duration::from_millis(100)),
+                    Err(e) => {
+                        warn!("Error checking kernel process status: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.process.kill().context("Failed to kill kernel process")?;
+        self.process.wait().context("Failed to wait for kernel process")?;
+        Ok(())
+    }
+}
+
+impl Drop for JupyterKernel {
+    fn drop(&mut self) {
+        if let Err(e) = self.shutdown() {
+            error!("Error shutting down Jupyter kernel {}: {}", self.id, e);
+        }
+    }
+}
+
+/// Decode one `iopub` multipart message into a `KernelOutput`, filtering to
+/// messages that are replies to `msg_id`. Returns `None` for messages to skip.
+fn decode_iopub_message(frames: &[Vec<u8>], msg_id: &str) -> Option<KernelOutput> {
+    // Frames: [identities...] <IDS|MSG> signature header parent_header metadata content
+    let delimiter_index = frames.iter().position(|f| f.as_slice() == DELIMITER)?;
+    let header: serde_json::Value = serde_json::from_slice(&frames[delimiter_index + 2]).ok()?;
+    let parent_header: serde_json::Value =
+        serde_json::from_slice(&frames[delimiter_index + 3]).ok()?;
+    let content: serde_json::Value = serde_json::from_slice(&frames[delimiter_index + 5]).ok()?;
+
+    if parent_header.get("msg_id").and_then(|v| v.as_str()) != Some(msg_id) {
+        return None;
+    }
+
+    match header.get("msg_type").and_then(|v| v.as_str())? {
+        "status" => {
+            if content.get("execution_state").and_then(|v| v.as_str()) == Some("idle") {
+                Some(KernelOutput::Idle)
+            } else {
+                None
+            }
+        }
+        "stream" => Some(KernelOutput::Stream {
+            name: content.get("name")?.as_str()?.to_string(),
+            text: content.get("text")?.as_str()?.to_string(),
+        }),
+        "execute_result" | "display_data" => {
+            let data = content.get("data")?.clone();
+
+            for mime in ["image/png", "image/jpeg"] {
+                if let Some(base64_data) = data.get(mime).and_then(|v| v.as_str()) {
+                    if let Some(image) = decode_image_output(base64_data) {
+                        return Some(KernelOutput::Image { image });
+                    }
+                }
+            }
+
+            Some(KernelOutput::Result { mime_bundle: data })
+        }
+        "error" => Some(KernelOutput::Error {
+            ename: content.get("ename")?.as_str()?.to_string(),
+            evalue: content.get("evalue")?.as_str()?.to_string(),
+            traceback: content
+                .get("traceback")?
+                .as_array()?
+                .iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect(),
+        }),
+        _ => None,
+    }
+}
+
+/// Decode a base64 PNG/JPEG `display_data` payload into the same
+/// `ImageData { rgba, width, height }` shape used by the clipboard commands
+fn decode_image_output(base64_data: &str) -> Option<ImageData> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(base64_data)
+        .ok()?;
+    let image = image::load_from_memory(&bytes).ok()?.to_rgba8();
+    let (width, height) = image.dimensions();
+
+    Some(ImageData {
+        rgba: image.into_raw(),
+        width,
+        height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hmac_sign_is_deterministic() {
+        let a = hmac_sign("secret", &[b"hello", b"world"]);
+        let b = hmac_sign("secret", &[b"hello", b"world"]);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_hmac_sign_differs_by_key() {
+        let a = hmac_sign("key-one", &[b"same content"]);
+        let b = hmac_sign("key-two", &[b"same content"]);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn test_hmac_sign_differs_by_content() {
+        let a = hmac_sign("secret", &[b"content-a"]);
+        let b = hmac_sign("secret", &[b"content-b"]);
+        assert_ne!(a, b);
+    }
+
+    fn frames_for(msg_type: &str, parent_msg_id: &str, content: serde_json::Value) -> Vec<Vec<u8>> {
+        let header = serde_json::json!({ "msg_type": msg_type }).to_string().into_bytes();
+        let parent_header = serde_json::json!({ "msg_id": parent_msg_id })
+            .to_string()
+            .into_bytes();
+        let metadata = serde_json::json!({}).to_string().into_bytes();
+        let content = content.to_string().into_bytes();
+
+        vec![
+            b"identity".to_vec(),
+            DELIMITER.to_vec(),
+            b"signature".to_vec(),
+            header,
+            parent_header,
+            metadata,
+            content,
+        ]
+    }
+
+    #[test]
+    fn test_decode_iopub_message_ignores_other_msg_ids() {
+        let frames = frames_for(
+            "stream",
+            "other-msg-id",
+            serde_json::json!({ "name": "stdout", "text": "hi" }),
+        );
+        assert!(decode_iopub_message(&frames, "our-msg-id").is_none());
+    }
+
+    #[test]
+    fn test_decode_iopub_message_idle_status() {
+        let frames = frames_for(
+            "status",
+            "our-msg-id",
+            serde_json::json!({ "execution_state": "idle" }),
+        );
+        assert!(matches!(
+            decode_iopub_message(&frames, "our-msg-id"),
+            Some(KernelOutput::Idle)
+        ));
+    }
+
+    #[test]
+    fn test_decode_iopub_message_busy_status_is_skipped() {
+        let frames = frames_for(
+            "status",
+            "our-msg-id",
+            serde_json::json!({ "execution_state": "busy" }),
+        );
+        assert!(decode_iopub_message(&frames, "our-msg-id").is_none());
+    }
+
+    #[test]
+    fn test_decode_iopub_message_stream() {
+        let frames = frames_for(
+            "stream",
+            "our-msg-id",
+            serde_json::json!({ "name": "stdout", "text": "hello\n" }),
+        );
+        match decode_iopub_message(&frames, "our-msg-id") {
+            Some(KernelOutput::Stream { name, text }) => {
+                assert_eq!(name, "stdout");
+                assert_eq!(text, "hello\n");
+            }
+            other => panic!("expected Stream output, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_iopub_message_error() {
+        let frames = frames_for(
+            "error",
+            "our-msg-id",
+            serde_json::json!({
+                "ename": "ValueError",
+                "evalue": "bad value",
+                "traceback": ["line 1", "line 2"],
+            }),
+        );
+        match decode_iopub_message(&frames, "our-msg-id") {
+            Some(KernelOutput::Error { ename, evalue, traceback }) => {
+                assert_eq!(ename, "ValueError");
+                assert_eq!(evalue, "bad value");
+                assert_eq!(traceback, vec!["line 1".to_string(), "line 2".to_string()]);
+            }
+            other => panic!("expected Error output, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_iopub_message_missing_delimiter() {
+        let frames = vec![b"no delimiter here".to_vec()];
+        assert!(decode_iopub_message(&frames, "our-msg-id").is_none());
+    }
+}
@@ -1,17 +1,38 @@
 use anyhow::{Context, Result};
 use log::{debug, info, warn, error};
+use shared_child::SharedChild;
 use std::path::PathBuf;
-use std::process::{Child, Command, Stdio};
+use std::process::{Command, Stdio};
+use std::sync::Arc;
 use tauri::AppHandle;
 
-use crate::python::health::{find_available_port, wait_for_backend_ready, HealthCheckConfig};
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+
+use crate::python::health::{find_available_port, is_port_available, wait_for_backend_ready, HealthCheckConfig};
 use crate::utils::db_path::get_database_path;
 
+/// Flag passed to `CreateProcess` so the child gets its own console process
+/// group, which is what lets us later target it (and not ourselves) with
+/// `GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, ...)` on Windows
+#[cfg(windows)]
+const CREATE_NEW_PROCESS_GROUP: u32 = 0x00000200;
+
 /// Python backend process manager
+///
+/// The child process is wrapped in a `SharedChild` so it can be waited on
+/// and signaled through a shared reference, which lets `is_running()` take
+/// `&self`
 pub struct PythonBackend {
-    process: Option<Child>,
+    process: Option<Arc<SharedChild>>,
     port: u16,
     base_url: String,
+    app_root: PathBuf,
+    /// The at-rest database path (possibly an encrypted container)
+    persistent_db_path: PathBuf,
+    /// The plaintext path actually handed to the backend via
+    /// `JTERM_DATABASE_PATH` -- see `db_crypto::prepare_runtime_database`
+    runtime_db_path: PathBuf,
 }
 
 impl PythonBackend {
@@ -20,12 +41,25 @@ impl PythonBackend {
     /// Finds an available port, starts the Python backend using bundled venv,
     /// and waits for the backend to be ready
     pub async fn launch(app_handle: &AppHandle) -> Result<Self> {
+        Self::launch_with_preferred_port(app_handle, None).await
+    }
+
+    /// Launch the backend, preferring `preferred_port` if it's still free
+    ///
+    /// Used by the dev-mode file watcher so a restart keeps the same port
+    /// where possible instead of handing the frontend a new one every reload
+    pub async fn launch_with_preferred_port(
+        app_handle: &AppHandle,
+        preferred_port: Option<u16>,
+    ) -> Result<Self> {
         info!("Launching Python backend...");
 
-        // Find available port in range 8000-9000
-        let port = find_available_port(8000, 9000)
-            .await
-            .context("No available ports found in range 8000-9000")?;
+        let port = match preferred_port {
+            Some(preferred) if is_port_available(preferred).await => preferred,
+            _ => find_available_port(8000, 9000)
+                .await
+                .context("No available ports found in range 8000-9000")?,
+        };
 
         info!("Using port {} for Python backend", port);
 
@@ -33,6 +67,12 @@ impl PythonBackend {
         let db_path = get_database_path(app_handle)?;
         info!("Database path: {:?}", db_path);
 
+        // The backend only speaks raw SQLite, so if the at-rest database is
+        // encrypted, decrypt it to a plaintext scratch copy and hand the
+        // backend that path instead; `shutdown()` re-encrypts it afterward
+        let runtime_db_path = crate::utils::db_crypto::prepare_runtime_database(&db_path)
+            .context("Failed to prepare database for backend")?;
+
         // Get the bundled Python interpreter and app root
         let (python_path, app_root) = get_python_paths(app_handle)?;
         info!("Python interpreter: {:?}", python_path);
@@ -63,16 +103,22 @@ impl PythonBackend {
             .arg("--port")
             .arg(port.to_string())
             .current_dir(&app_root)
-            .env("JTERM_DATABASE_PATH", db_path.to_str().unwrap())
+            .env("JTERM_DATABASE_PATH", runtime_db_path.to_str().unwrap())
             .env("JTERM_DESKTOP_MODE", "1")
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
+        // Give the child its own process group on Windows so a later
+        // CTRL_BREAK_EVENT targets it (and any children it spawns) instead
+        // of also hitting us
+        #[cfg(windows)]
+        command.creation_flags(CREATE_NEW_PROCESS_GROUP);
+
         debug!("Starting Python backend with command: {:?}", command);
 
-        let child = command
-            .spawn()
-            .context("Failed to spawn Python backend process")?;
+        let child = Arc::new(
+            SharedChild::spawn(&mut command).context("Failed to spawn Python backend process")?,
+        );
 
         info!("Python backend process started (PID: {:?})", child.id());
 
@@ -89,6 +135,9 @@ impl PythonBackend {
             process: Some(child),
             port,
             base_url,
+            app_root,
+            persistent_db_path: db_path,
+            runtime_db_path,
         })
     }
 
@@ -97,29 +146,45 @@ impl PythonBackend {
         &self.base_url
     }
 
+    /// Get the app root directory the backend was launched from
+    pub fn app_root(&self) -> &PathBuf {
+        &self.app_root
+    }
+
     /// Get the backend port
     pub fn port(&self) -> u16 {
         self.port
     }
 
     /// Check if the backend process is still running
-    pub fn is_running(&mut self) -> bool {
-        if let Some(ref mut child) = self.process {
-            match child.try_wait() {
+    ///
+    /// Shared (not exclusive) access since `SharedChild::try_wait` needs no
+    /// `&mut`, so the crash supervisor can poll this through a shared borrow
+    pub fn is_running(&self) -> bool {
+        match self.process.as_ref() {
+            Some(child) => match child.try_wait() {
                 Ok(Some(_)) => false, // Process has exited
                 Ok(None) => true,     // Process is still running
                 Err(_) => false,      // Error checking status
-            }
-        } else {
-            false
+            },
+            None => false,
         }
     }
 
     /// Gracefully shutdown the Python backend
+    ///
+    /// Re-encrypts the database at rest before returning (see
+    /// `db_crypto::finalize_runtime_database`) whether the process exited
+    /// gracefully, had already exited, or was force-killed, so a crashed or
+    /// force-killed backend doesn't leave the plaintext scratch copy lying
+    /// around. If the force kill (or the wait after it) fails, that error is
+    /// returned and the database is left as it is.
     pub fn shutdown(&mut self) -> Result<()> {
         info!("Shutting down Python backend...");
 
-        if let Some(mut child) = self.process.take() {
+        if let Some(child) = self.process.take() {
+            let mut exited_gracefully = false;
+
             // Try graceful shutdown first (platform-specific)
             #[cfg(unix)]
             {
@@ -132,7 +197,8 @@ impl PythonBackend {
                     match child.try_wait() {
                         Ok(Some(_)) => {
                             info!("Python backend shut down gracefully");
-                            return Ok(());
+                            exited_gracefully = true;
+                            break;
                         }
                         Ok(None) => {
                             std::thread::sleep(std::time::Duration::from_millis(100));
@@ -145,11 +211,48 @@ impl PythonBackend {
                 }
             }
 
-            // Force kill if graceful shutdown failed
-            warn!("Force killing Python backend process");
-            child.kill().context("Failed to kill Python backend")?;
-            child.wait().context("Failed to wait for Python backend")?;
-            info!("Python backend process terminated");
+            // On Windows, ask the process group to shut down gracefully via
+            // CTRL_BREAK_EVENT before resorting to a hard kill. Requires the
+            // child to have been spawned with CREATE_NEW_PROCESS_GROUP,
+            // otherwise the signal would also land on us.
+            #[cfg(windows)]
+            {
+                use windows_sys::Win32::System::Console::{GenerateConsoleCtrlEvent, CTRL_BREAK_EVENT};
+
+                unsafe {
+                    GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, child.id());
+                }
+                for _ in 0..50 {
+                    match child.try_wait() {
+                        Ok(Some(_)) => {
+                            info!("Python backend shut down gracefully");
+                            exited_gracefully = true;
+                            break;
+                        }
+                        Ok(None) => {
+                            std::thread::sleep(std::time::Duration::from_millis(100));
+                        }
+                        Err(e) => {
+                            warn!("Error checking backend process status: {}", e);
+                            break;
+                        }
+                    }
+                }
+            }
+
+            if !exited_gracefully {
+                warn!("Force killing Python backend process");
+                child.kill().context("Failed to kill Python backend")?;
+                child.wait().context("Failed to wait for Python backend")?;
+                info!("Python backend process terminated");
+            }
+        }
+
+        if let Err(e) = crate::utils::db_crypto::finalize_runtime_database(
+            &self.runtime_db_path,
+            &self.persistent_db_path,
+        ) {
+            warn!("Failed to re-encrypt database after backend shutdown: {}", e);
         }
 
         Ok(())
@@ -164,6 +267,12 @@ impl Drop for PythonBackend {
     }
 }
 
+/// Resolve just the Python interpreter path, for callers (like the Jupyter
+/// kernel subsystem) that don't need the app root directory too
+pub fn find_python_interpreter(app_handle: &AppHandle) -> Result<PathBuf> {
+    get_python_paths(app_handle).map(|(python_path, _)| python_path)
+}
+
 /// Get the path to the bundled Python interpreter and app root directory
 /// Returns (python_path, app_root)
 fn get_python_paths(_app_handle: &AppHandle) -> Result<(PathBuf, PathBuf)> {
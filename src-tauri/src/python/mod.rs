@@ -0,0 +1,7 @@
+// Python backend process management
+// Launching/health-checking the embedded web server and Jupyter kernels
+
+pub mod health;
+pub mod kernel;
+pub mod launcher;
+pub mod watcher;
@@ -0,0 +1,117 @@
+// Dev-mode file watcher
+// Restarts the Python backend when its source changes, so `cargo tauri dev`
+// doesn't require a full app restart to pick up backend edits
+
+#![cfg(debug_assertions)]
+
+use log::{error, info, warn};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Mutex;
+
+use crate::python::launcher::PythonBackend;
+use crate::utils::logging::DesktopLogger;
+
+/// Debounce window for coalescing a burst of file-change events into one restart
+const DEBOUNCE_WINDOW_MS: u64 = 200;
+
+/// Watch `app_root/src` and restart the backend on change
+///
+/// Only compiled in debug builds; gracefully no-ops if the `src` directory
+/// doesn't exist (e.g. a production-style layout running in a debug build)
+pub fn spawn_dev_watcher(
+    app_handle: AppHandle,
+    backend_mutex: Arc<Mutex<Option<PythonBackend>>>,
+    logger: Arc<DesktopLogger>,
+    app_root: std::path::PathBuf,
+) {
+    let watch_path = app_root.join("src");
+    if !watch_path.exists() {
+        warn!("Dev watcher: {:?} does not exist, not watching", watch_path);
+        return;
+    }
+
+    std::thread::spawn(move || {
+        let (tx, rx) = std::sync::mpsc::channel();
+
+        let mut watcher: RecommendedWatcher =
+            match notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+                if let Ok(event) = res {
+                    let _ = tx.send(event);
+                }
+            }) {
+                Ok(watcher) => watcher,
+                Err(e) => {
+                    error!("Dev watcher: failed to create file watcher: {}", e);
+                    return;
+                }
+            };
+
+        if let Err(e) = watcher.watch(&watch_path, RecursiveMode::Recursive) {
+            error!("Dev watcher: failed to watch {:?}: {}", watch_path, e);
+            return;
+        }
+
+        info!("Dev watcher: watching {:?} for Python source changes", watch_path);
+
+        loop {
+            // Block until the first change, then drain any burst within the debounce window
+            if rx.recv().is_err() {
+                break;
+            }
+            while rx
+                .recv_timeout(Duration::from_millis(DEBOUNCE_WINDOW_MS))
+                .is_ok()
+            {}
+
+            info!("Dev watcher: source change detected, restarting Python backend");
+
+            tauri::async_runtime::block_on(restart_backend(
+                &app_handle,
+                &backend_mutex,
+                &logger,
+            ));
+        }
+    });
+}
+
+async fn restart_backend(
+    app_handle: &AppHandle,
+    backend_mutex: &Arc<Mutex<Option<PythonBackend>>>,
+    logger: &DesktopLogger,
+) {
+    let mut backend_guard = backend_mutex.lock().await;
+    let previous_port = backend_guard.as_ref().map(|b| b.port());
+
+    if let Some(mut backend) = backend_guard.take() {
+        if let Err(e) = backend.shutdown() {
+            error!("Dev watcher: error shutting down backend for reload: {}", e);
+        }
+    }
+
+    match PythonBackend::launch_with_preferred_port(app_handle, previous_port).await {
+        Ok(new_backend) => {
+            let port = new_backend.port();
+            let base_url = new_backend.base_url().to_string();
+            *backend_guard = Some(new_backend);
+            drop(backend_guard);
+
+            logger.log_backend_reload(port);
+            info!("Dev watcher: backend reloaded at {}", base_url);
+
+            if let Some(window) = app_handle.get_webview_window("main") {
+                if let Err(e) = window.navigate(tauri::Url::parse(&base_url).unwrap()) {
+                    error!("Dev watcher: failed to re-navigate after reload: {}", e);
+                }
+            }
+
+            let _ = app_handle.emit("dev-watcher://backend-reloaded", serde_json::json!({ "port": port }));
+        }
+        Err(e) => {
+            error!("Dev watcher: failed to relaunch backend: {}", e);
+            logger.log_error(&format!("Dev watcher failed to relaunch backend: {}", e));
+        }
+    }
+}
@@ -0,0 +1,160 @@
+// Launch-at-login support
+// Registers/deregisters jterm with the OS-native startup mechanism
+
+use anyhow::{Context, Result};
+use log::info;
+use std::path::PathBuf;
+use tauri::AppHandle;
+
+const APP_IDENTIFIER: &str = "com.jterm.app";
+
+/// Resolve the path to the currently running executable
+fn executable_path(_app_handle: &AppHandle) -> Result<PathBuf> {
+    std::env::current_exe().context("Failed to resolve the running executable path")
+}
+
+/// Enable launch-at-login using the platform's startup mechanism
+///
+/// Idempotent: calling this when already enabled just rewrites the same entry
+pub fn enable(app_handle: &AppHandle) -> Result<()> {
+    let exe_path = executable_path(app_handle)?;
+    info!("Enabling launch at login for {:?}", exe_path);
+
+    #[cfg(target_os = "macos")]
+    {
+        let plist_path = launch_agent_path()?;
+        let plist = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key>
+    <string>{identifier}</string>
+    <key>ProgramArguments</key>
+    <array>
+        <string>{exe}</string>
+    </array>
+    <key>RunAtLoad</key>
+    <true/>
+</dict>
+</plist>
+"#,
+            identifier = APP_IDENTIFIER,
+            exe = exe_path.to_string_lossy(),
+        );
+
+        if let Some(parent) = plist_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&plist_path, plist)
+            .with_context(|| format!("Failed to write LaunchAgent at {:?}", plist_path))?;
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let (key, _) = windows_run_key()?;
+        key.set_value("jterm", &exe_path.to_string_lossy().to_string())
+            .context("Failed to set Windows Run registry value")?;
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let desktop_path = autostart_desktop_path()?;
+        let desktop_entry = format!(
+            "[Desktop Entry]\nType=Application\nName=jterm\nExec={exe}\nX-GNOME-Autostart-enabled=true\n",
+            exe = exe_path.to_string_lossy(),
+        );
+
+        if let Some(parent) = desktop_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&desktop_path, desktop_entry)
+            .with_context(|| format!("Failed to write autostart entry at {:?}", desktop_path))?;
+    }
+
+    Ok(())
+}
+
+/// Disable launch-at-login
+///
+/// Idempotent: calling this when already disabled is a no-op
+pub fn disable(_app_handle: &AppHandle) -> Result<()> {
+    info!("Disabling launch at login");
+
+    #[cfg(target_os = "macos")]
+    {
+        let plist_path = launch_agent_path()?;
+        if plist_path.exists() {
+            std::fs::remove_file(&plist_path)
+                .with_context(|| format!("Failed to remove LaunchAgent at {:?}", plist_path))?;
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let (key, _) = windows_run_key()?;
+        let _ = key.delete_value("jterm");
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let desktop_path = autostart_desktop_path()?;
+        if desktop_path.exists() {
+            std::fs::remove_file(&desktop_path).with_context(|| {
+                format!("Failed to remove autostart entry at {:?}", desktop_path)
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Check whether jterm is currently registered to launch at login
+pub fn is_enabled(_app_handle: &AppHandle) -> Result<bool> {
+    #[cfg(target_os = "macos")]
+    {
+        return Ok(launch_agent_path()?.exists());
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let (key, _) = windows_run_key()?;
+        return Ok(key.get_value::<String, _>("jterm").is_ok());
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        return Ok(autostart_desktop_path()?.exists());
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn launch_agent_path() -> Result<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Failed to resolve home directory"))?;
+    Ok(home
+        .join("Library")
+        .join("LaunchAgents")
+        .join(format!("{}.plist", APP_IDENTIFIER)))
+}
+
+#[cfg(target_os = "windows")]
+fn windows_run_key() -> Result<(winreg::RegKey, winreg::enums::RegType)> {
+    use winreg::enums::*;
+    use winreg::RegKey;
+
+    let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+    let key = hkcu
+        .open_subkey_with_flags(
+            r"Software\Microsoft\Windows\CurrentVersion\Run",
+            KEY_READ | KEY_WRITE,
+        )
+        .context("Failed to open Windows Run registry key")?;
+    Ok((key, REG_SZ))
+}
+
+#[cfg(target_os = "linux")]
+fn autostart_desktop_path() -> Result<PathBuf> {
+    let config_dir = dirs::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("Failed to resolve XDG config directory"))?;
+    Ok(config_dir.join("autostart").join("jterm.desktop"))
+}
@@ -0,0 +1,301 @@
+// Database encryption at rest
+// Encrypts `webterminal.db` with a key held in the OS secret store
+// (macOS Keychain, Windows Credential Manager, Linux Secret Service), so the
+// file is unusable if copied off the machine without that entry
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use anyhow::{Context, Result};
+use log::info;
+use rand::RngCore;
+use std::path::{Path, PathBuf};
+use tauri::AppHandle;
+
+use crate::utils::db_path::get_database_path;
+
+const KEYCHAIN_SERVICE: &str = "jterm";
+const KEYCHAIN_ACCOUNT: &str = "db-data-key";
+
+/// Magic header written at the start of an encrypted database file so
+/// `is_db_encrypted` can tell an encrypted file from a plaintext SQLite file
+const ENCRYPTED_MAGIC: &[u8; 8] = b"JTERMDB1";
+
+/// Generate a random 32-byte data key and store it in the platform secret
+/// store (macOS Keychain, Windows Credential Manager, Linux Secret Service)
+///
+/// Idempotent: returns the existing key if one is already stored
+fn get_or_create_keychain_key() -> Result<[u8; 32]> {
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_ACCOUNT)
+        .context("Failed to open platform secret store entry")?;
+
+    match entry.get_password() {
+        Ok(encoded) => {
+            let bytes = hex_decode(&encoded).context("Keychain data key is corrupt")?;
+            let mut key = [0u8; 32];
+            key.copy_from_slice(&bytes);
+            Ok(key)
+        }
+        Err(keyring::Error::NoEntry) => {
+            info!("No database data key found in secret store, generating one");
+            let mut key = [0u8; 32];
+            rand::thread_rng().fill_bytes(&mut key);
+            entry
+                .set_password(&hex_encode(&key))
+                .context("Failed to persist data key to secret store")?;
+            Ok(key)
+        }
+        Err(e) => Err(e).context("Failed to read data key from secret store"),
+    }
+}
+
+/// Look up the database encryption key from the platform secret store
+///
+/// The database is unusable on another machine because the keychain entry
+/// does not travel with the file.
+pub fn derive_database_key() -> Result<[u8; 32]> {
+    get_or_create_keychain_key()
+}
+
+/// Check whether the database at `db_path` is already encrypted
+pub fn is_db_encrypted(db_path: &Path) -> Result<bool> {
+    if !db_path.exists() {
+        return Ok(false);
+    }
+
+    let mut header = [0u8; 8];
+    let mut file = std::fs::File::open(db_path).context("Failed to open database file")?;
+    use std::io::Read;
+    let read = file.read(&mut header)?;
+    Ok(read == 8 && &header == ENCRYPTED_MAGIC)
+}
+
+fn encrypt_bytes(key: &[u8; 32], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+    let mut nonce_bytes = [0u8; 12];
+    rand::thread_rng().fill_bytes(&mut nonce_bytes);
+    let nonce = Nonce::from_slice(&nonce_bytes);
+
+    let ciphertext = cipher
+        .encrypt(nonce, plaintext)
+        .map_err(|e| anyhow::anyhow!("Failed to encrypt database: {}", e))?;
+
+    let mut out = Vec::with_capacity(8 + 12 + ciphertext.len());
+    out.extend_from_slice(ENCRYPTED_MAGIC);
+    out.extend_from_slice(&nonce_bytes);
+    out.extend_from_slice(&ciphertext);
+    Ok(out)
+}
+
+/// Decrypt a database file previously written by `encrypt_bytes`
+pub fn decrypt_bytes(key: &[u8; 32], ciphertext: &[u8]) -> Result<Vec<u8>> {
+    anyhow::ensure!(
+        ciphertext.len() > 20 && &ciphertext[0..8] == ENCRYPTED_MAGIC,
+        "Database is not in the expected encrypted format"
+    );
+
+    let nonce = Nonce::from_slice(&ciphertext[8..20]);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+
+    cipher
+        .decrypt(nonce, &ciphertext[20..])
+        .map_err(|e| anyhow::anyhow!("Failed to decrypt database (wrong key?): {}", e))
+}
+
+/// Stage path used while atomically swapping an encrypted copy into place
+fn staging_path(db_path: &Path) -> PathBuf {
+    db_path.with_extension("db.encrypting")
+}
+
+/// Path to the plaintext working copy the Python backend opens directly
+/// while the at-rest database is encrypted. The backend only knows how to
+/// speak raw SQLite over `JTERM_DATABASE_PATH`, not our AES-GCM container,
+/// so it never sees `db_path` itself in that case -- see
+/// `prepare_runtime_database`/`finalize_runtime_database`.
+fn runtime_copy_path(db_path: &Path) -> PathBuf {
+    db_path.with_extension("db.runtime")
+}
+
+/// Encrypt the plaintext database at `db_path` in place, if it exists and
+/// isn't encrypted already. Stages the ciphertext beside the database and
+/// atomically renames it in, so a crash mid-write can't corrupt the live db.
+fn encrypt_in_place(db_path: &Path) -> Result<()> {
+    if !db_path.exists() || is_db_encrypted(db_path)? {
+        return Ok(());
+    }
+
+    let key = derive_database_key()?;
+    let plaintext = std::fs::read(db_path).context("Failed to read plaintext database")?;
+    let ciphertext = encrypt_bytes(&key, &plaintext)?;
+
+    let staged_path = staging_path(db_path);
+    std::fs::write(&staged_path, &ciphertext)
+        .with_context(|| format!("Failed to stage encrypted database at {:?}", staged_path))?;
+    std::fs::rename(&staged_path, db_path)
+        .context("Failed to atomically swap in the encrypted database")?;
+    Ok(())
+}
+
+/// Migrate an existing plaintext database to the encrypted format in place
+pub async fn migrate_to_encrypted(app_handle: &AppHandle) -> Result<()> {
+    let db_path = get_database_path(app_handle)?;
+
+    if is_db_encrypted(&db_path)? {
+        info!("Database is already encrypted, skipping migration");
+        return Ok(());
+    }
+
+    if !db_path.exists() {
+        info!("No existing database to migrate");
+        return Ok(());
+    }
+
+    info!("Migrating database to encrypted format");
+    encrypt_in_place(&db_path)?;
+    info!("Database migration to encrypted format complete");
+    Ok(())
+}
+
+/// Prepare the file the Python backend should open directly as SQLite
+///
+/// The backend has no notion of our AES-GCM container, so if the at-rest
+/// database is encrypted this decrypts it into a plaintext scratch copy
+/// beside it and hands back that path for `JTERM_DATABASE_PATH` instead. A
+/// fresh or already-plaintext database is handed back unchanged -- it gets
+/// encrypted for the first time by `finalize_runtime_database` once the
+/// backend shuts down cleanly.
+pub fn prepare_runtime_database(db_path: &Path) -> Result<PathBuf> {
+    if !db_path.exists() || !is_db_encrypted(db_path)? {
+        return Ok(db_path.to_path_buf());
+    }
+
+    info!("Decrypting database for backend startup");
+    let key = derive_database_key()?;
+    let ciphertext = std::fs::read(db_path).context("Failed to read encrypted database")?;
+    let plaintext = decrypt_bytes(&key, &ciphertext)?;
+
+    let runtime_path = runtime_copy_path(db_path);
+    std::fs::write(&runtime_path, &plaintext)
+        .with_context(|| format!("Failed to write decrypted database to {:?}", runtime_path))?;
+    Ok(runtime_path)
+}
+
+/// Re-encrypt the backend's plaintext working copy back onto `db_path` and
+/// remove the scratch copy, undoing `prepare_runtime_database`
+///
+/// Called from `PythonBackend::shutdown` so the database is encrypted at
+/// rest as soon as the backend is done with it. If `runtime_path` and
+/// `db_path` are the same (the database started out plaintext, so
+/// `prepare_runtime_database` handed the backend `db_path` directly), this
+/// just encrypts whatever the backend wrote, in place.
+pub fn finalize_runtime_database(runtime_path: &Path, db_path: &Path) -> Result<()> {
+    if runtime_path == db_path {
+        return encrypt_in_place(db_path);
+    }
+
+    if !runtime_path.exists() {
+        // Backend never wrote anything (e.g. it failed to start)
+        return Ok(());
+    }
+
+    info!("Re-encrypting database after backend shutdown");
+    let key = derive_database_key()?;
+    let plaintext = std::fs::read(runtime_path).context("Failed to read decrypted database")?;
+    let ciphertext = encrypt_bytes(&key, &plaintext)?;
+
+    let staged_path = staging_path(db_path);
+    std::fs::write(&staged_path, &ciphertext)
+        .with_context(|| format!("Failed to stage encrypted database at {:?}", staged_path))?;
+    std::fs::rename(&staged_path, db_path)
+        .context("Failed to atomically swap in the encrypted database")?;
+
+    std::fs::remove_file(runtime_path).context("Failed to remove decrypted scratch database")?;
+    Ok(())
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn hex_decode(s: &str) -> Result<Vec<u8>> {
+    anyhow::ensure!(s.len() % 2 == 0, "Hex string has odd length");
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).context("Invalid hex digit"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let key = [7u8; 32];
+        let plaintext = b"SQLite format 3\0some row data".to_vec();
+
+        let ciphertext = encrypt_bytes(&key, &plaintext).unwrap();
+        assert!(ciphertext.starts_with(ENCRYPTED_MAGIC));
+
+        let decrypted = decrypt_bytes(&key, &ciphertext).unwrap();
+        assert_eq!(decrypted, plaintext);
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_key() {
+        let plaintext = b"secret rows".to_vec();
+        let ciphertext = encrypt_bytes(&[1u8; 32], &plaintext).unwrap();
+
+        assert!(decrypt_bytes(&[2u8; 32], &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_decrypt_rejects_bad_magic() {
+        let mut ciphertext = encrypt_bytes(&[3u8; 32], b"data").unwrap();
+        ciphertext[0] = b'X';
+
+        assert!(decrypt_bytes(&[3u8; 32], &ciphertext).is_err());
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        let encoded = hex_encode(&bytes);
+        let decoded = hex_decode(&encoded).unwrap();
+        assert_eq!(decoded, bytes);
+    }
+
+    #[test]
+    fn test_hex_decode_rejects_odd_length() {
+        assert!(hex_decode("abc").is_err());
+    }
+
+    #[test]
+    fn test_is_db_encrypted_detects_magic_header() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("jterm_db_crypto_test_{}.db", std::process::id()));
+
+        std::fs::write(&path, b"SQLite format 3\0").unwrap();
+        assert!(!is_db_encrypted(&path).unwrap());
+
+        std::fs::write(&path, encrypt_bytes(&[9u8; 32], b"rows").unwrap()).unwrap();
+        assert!(is_db_encrypted(&path).unwrap());
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_is_db_encrypted_missing_file_is_false() {
+        let mut path = std::env::temp_dir();
+        path.push(format!("jterm_db_crypto_test_missing_{}.db", std::process::id()));
+
+        assert!(!is_db_encrypted(&path).unwrap());
+    }
+
+    #[test]
+    fn test_runtime_copy_path_differs_from_db_path() {
+        let db_path = PathBuf::from("/tmp/webterminal.db");
+        assert_ne!(runtime_copy_path(&db_path), db_path);
+        assert_ne!(staging_path(&db_path), db_path);
+    }
+}
@@ -69,6 +69,14 @@ impl DesktopLogger {
         self.write_to_file("INFO", &format!("Python backend ready on port {}", port));
     }
 
+    pub fn log_backend_reload(&self, port: u16) {
+        info!("Python backend reloaded on port {} after source change", port);
+        self.write_to_file(
+            "INFO",
+            &format!("Python backend reloaded on port {} after source change", port),
+        );
+    }
+
     pub fn log_error(&self, message: &str) {
         error!("{}", message);
         self.write_to_file("ERROR", message);
@@ -0,0 +1,8 @@
+// Shared utility modules
+// Cross-cutting helpers that don't belong to a single command surface
+
+pub mod autostart;
+pub mod db_crypto;
+pub mod db_path;
+pub mod logging;
+pub mod updater;
@@ -0,0 +1,271 @@
+// Self-update subsystem
+// Polls a release manifest, verifies the advertised artifact against a
+// compiled-in Ed25519 public key, and stages it for installation
+
+use anyhow::{Context, Result};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter};
+
+use crate::utils::db_path::get_temp_directory;
+
+/// URL of the signed release manifest, overridable for testing/staging channels
+const DEFAULT_MANIFEST_URL: &str = "https://updates.jterm.dev/manifest.json";
+
+/// Ed25519 public key used to verify release artifacts, embedded at build time
+const UPDATE_PUBLIC_KEY: [u8; 32] = [
+    0x3b, 0x6a, 0x27, 0xbc, 0xce, 0xb6, 0xa4, 0x2d, 0x62, 0xa3, 0xa8, 0xd0, 0x2a, 0x6f, 0x0d, 0x73,
+    0x65, 0x32, 0x15, 0x77, 0x1d, 0xe2, 0x43, 0xa6, 0x3a, 0xc0, 0x48, 0xa1, 0x8b, 0x59, 0xda, 0x29,
+];
+
+/// One downloadable artifact for a given platform target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlatformArtifact {
+    pub url: String,
+    /// Base64-encoded detached Ed25519 signature over the artifact bytes
+    pub signature: String,
+}
+
+/// Release manifest served by `DEFAULT_MANIFEST_URL`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseManifest {
+    pub version: String,
+    pub notes: Option<String>,
+    pub platforms: HashMap<String, PlatformArtifact>,
+}
+
+/// Result of comparing the running version against the manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateInfo {
+    pub current_version: String,
+    pub available_version: String,
+    pub notes: Option<String>,
+}
+
+/// Update lifecycle events emitted to the frontend as they happen
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum UpdateProgress {
+    Checking,
+    UpToDate,
+    Available { version: String },
+    Downloading { downloaded: u64, total: Option<u64> },
+    Verifying,
+    Staged { path: String },
+    Failed { message: String },
+}
+
+fn emit_progress(app: &AppHandle, progress: UpdateProgress) {
+    if let Err(e) = app.emit("updater://progress", &progress) {
+        warn!("Failed to emit update progress: {}", e);
+    }
+}
+
+/// Resolve the manifest key for the running platform (`darwin`/`windows`/`linux`)
+fn current_target() -> &'static str {
+    #[cfg(target_os = "macos")]
+    return "darwin";
+
+    #[cfg(target_os = "windows")]
+    return "windows";
+
+    #[cfg(target_os = "linux")]
+    return "linux";
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    compile_error!("Unsupported platform");
+}
+
+/// Expected artifact extension for the running platform, mirroring the
+/// bundle matrix produced by the release pipeline
+fn expected_extension() -> &'static str {
+    #[cfg(target_os = "macos")]
+    return ".app.tar.gz";
+
+    #[cfg(target_os = "windows")]
+    return ".msi";
+
+    #[cfg(target_os = "linux")]
+    return ".AppImage.tar.gz";
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    compile_error!("Unsupported platform");
+}
+
+fn manifest_url() -> String {
+    std::env::var("JTERM_UPDATE_MANIFEST_URL").unwrap_or_else(|_| DEFAULT_MANIFEST_URL.to_string())
+}
+
+/// Fetch the release manifest and compare it against the running version
+///
+/// Returns `Ok(None)` when already up to date
+pub async fn check_for_updates(app: &AppHandle) -> Result<Option<UpdateInfo>> {
+    emit_progress(app, UpdateProgress::Checking);
+
+    let url = manifest_url();
+    info!("Checking for updates at {}", url);
+
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .build()?;
+
+    let manifest: ReleaseManifest = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch release manifest")?
+        .error_for_status()
+        .context("Release manifest request failed")?
+        .json()
+        .await
+        .context("Failed to parse release manifest")?;
+
+    let current_version = env!("CARGO_PKG_VERSION").to_string();
+
+    // Compare dotted numeric components ("1.10.0" > "1.9.2"); anything after
+    // the numeric part (e.g. a "-beta" suffix) is ignored
+    let numeric_parts = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(|c: char| c == '.' || c == '-' || c == '+')
+            .map_while(|part| part.parse().ok())
+            .collect()
+    };
+
+    if numeric_parts(&manifest.version) <= numeric_parts(&current_version) {
+        info!("jterm is up to date ({})", current_version);
+        emit_progress(app, UpdateProgress::UpToDate);
+        return Ok(None);
+    }
+
+    info!(
+        "Update available: {} -> {}",
+        current_version, manifest.version
+    );
+    emit_progress(
+        app,
+        UpdateProgress::Available {
+            version: manifest.version.clone(),
+        },
+    );
+
+    Ok(Some(UpdateInfo {
+        current_version,
+        available_version: manifest.version,
+        notes: manifest.notes,
+    }))
+}
+
+/// Download, verify, and stage the platform-appropriate update artifact
+///
+/// Once the artifact is staged, shuts down the running `PythonBackend` via
+/// `backend_shutdown`. Does not relaunch the backend or the app, and does not
+/// unpack or apply the staged file. Returns the staged file path.
+///
+/// `backend_shutdown` is awaited in place rather than taking a sync `FnOnce`
+/// run via `async_runtime::block_on` -- this function is itself an `.await`ed
+/// async fn, and blocking on another future from inside one already being
+/// driven by the runtime is the anti-pattern Tokio warns against.
+pub async fn install_update<F, Fut>(app: &AppHandle, backend_shutdown: F) -> Result<std::path::PathBuf>
+where
+    F: FnOnce() -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    let url = manifest_url();
+    let client = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(60))
+        .build()?;
+
+    let manifest: ReleaseManifest = client
+        .get(&url)
+        .send()
+        .await
+        .context("Failed to fetch release manifest")?
+        .json()
+        .await
+        .context("Failed to parse release manifest")?;
+
+    let target = current_target();
+    let artifact = manifest
+        .platforms
+        .get(target)
+        .ok_or_else(|| anyhow::anyhow!("No update artifact published for target {}", target))?;
+
+    info!("Downloading update artifact for {} from {}", target, artifact.url);
+
+    let response = client
+        .get(&artifact.url)
+        .send()
+        .await
+        .context("Failed to download update artifact")?;
+    let total = response.content_length();
+
+    use futures_util::StreamExt;
+
+    let mut downloaded = 0u64;
+    let mut bytes: Vec<u8> = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.context("Error while downloading update artifact")?;
+        downloaded += chunk.len() as u64;
+        bytes.extend_from_slice(&chunk);
+        emit_progress(app, UpdateProgress::Downloading { downloaded, total });
+    }
+
+    emit_progress(app, UpdateProgress::Verifying);
+
+    let signature_bytes = base64_decode(&artifact.signature)
+        .context("Update signature is not valid base64")?;
+    let signature = Signature::from_slice(&signature_bytes)
+        .context("Update signature has an invalid length")?;
+    let verifying_key = VerifyingKey::from_bytes(&UPDATE_PUBLIC_KEY)
+        .context("Compiled-in update public key is invalid")?;
+
+    if verifying_key.verify(&bytes, &signature).is_err() {
+        let message = "Update artifact failed signature verification".to_string();
+        error!("{}", message);
+        emit_progress(app, UpdateProgress::Failed { message: message.clone() });
+        anyhow::bail!(message);
+    }
+
+    if !artifact.url.ends_with(expected_extension()) {
+        warn!(
+            "Update artifact URL does not end with the expected extension {}",
+            expected_extension()
+        );
+    }
+
+    let staging_dir = get_temp_directory(app)?;
+    let file_name = artifact
+        .url
+        .rsplit('/')
+        .next()
+        .unwrap_or("jterm-update.bin");
+    let staged_path = staging_dir.join(file_name);
+    std::fs::write(&staged_path, &bytes)
+        .with_context(|| format!("Failed to stage update artifact at {:?}", staged_path))?;
+
+    info!("Update artifact verified and staged at {:?}", staged_path);
+    emit_progress(
+        app,
+        UpdateProgress::Staged {
+            path: staged_path.to_string_lossy().to_string(),
+        },
+    );
+
+    backend_shutdown()
+        .await
+        .context("Failed to shut down Python backend before applying update")?;
+
+    Ok(staged_path)
+}
+
+/// Decode a standard-alphabet base64 string with the `base64` crate (the
+/// same crate the kernel subsystem uses)
+fn base64_decode(input: &str) -> Result<Vec<u8>> {
+    use base64::Engine;
+    base64::engine::general_purpose::STANDARD
+        .decode(input)
+        .context("Invalid base64 payload")
+}